@@ -0,0 +1,25 @@
+//! Runtime API definition for asset registry module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::Vec;
+use xcm::VersionedMultiLocation;
+
+sp_api::decl_runtime_apis! {
+	pub trait AssetRegistryApi<AssetId, AssetMetadata> where
+		AssetId: Codec,
+		AssetMetadata: Codec,
+	{
+		fn assets(start_key: Option<AssetId>, limit: u32) -> Vec<(AssetId, AssetMetadata)>;
+		fn metadata_paged(start_key: Option<AssetId>, limit: u32) -> (Vec<(AssetId, AssetMetadata)>, Option<AssetId>);
+		fn asset_metadata(asset_id: AssetId) -> Option<AssetMetadata>;
+		fn metadata_by_location(location: VersionedMultiLocation) -> Option<AssetMetadata>;
+		fn asset_id(location: VersionedMultiLocation) -> Option<AssetId>;
+		fn asset_id_by_symbol(symbol: Vec<u8>) -> Option<AssetId>;
+	}
+}
@@ -0,0 +1,271 @@
+//! Benchmarks for the asset registry.
+//!
+//! Names and symbols always have the maximum length allowed by
+//! `Config::StringLimit` and assets have a location, so that every benchmark
+//! measures the worst case.
+
+use super::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::vec;
+
+fn location(index: u128) -> VersionedMultiLocation {
+	MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(index))).into()
+}
+
+/// A string of maximum length that is unique for each index, so that symbols
+/// don't conflict if `EnforceUniqueSymbol` is enabled.
+fn bounded_string<T: Config>(index: u32) -> BoundedVec<u8, T::StringLimit> {
+	let limit = T::StringLimit::get() as usize;
+	let index = index.to_le_bytes();
+	let len = limit.min(index.len());
+
+	let mut string = vec![b'a'; limit];
+	string[limit - len..].copy_from_slice(&index[..len]);
+	string.try_into().unwrap()
+}
+
+fn metadata<T: Config>(index: u32) -> AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>
+where
+	T::CustomMetadata: Default,
+{
+	AssetMetadata {
+		decimals: 12,
+		name: bounded_string::<T>(index),
+		symbol: bounded_string::<T>(index),
+		existential_deposit: 1u32.into(),
+		location: Some(location(index.into())),
+		additional: Default::default(),
+	}
+}
+
+fn register<T: Config>(asset_id: u32) -> T::AssetId
+where
+	T::AssetId: From<u32>,
+	T::CustomMetadata: Default,
+{
+	Pallet::<T>::do_register_asset_without_asset_processor(metadata::<T>(asset_id), asset_id.into()).unwrap()
+}
+
+/// Give an asset `n` additional locations, distinct from the locations of
+/// other assets as long as `offset` is.
+fn add_locations<T: Config>(asset_id: &T::AssetId, offset: u128, n: u32) {
+	for i in 0..n {
+		let index = u128::MAX - offset - i as u128;
+		Pallet::<T>::do_add_location(asset_id.clone(), location(index)).unwrap();
+	}
+}
+
+/// The origin that registers assets, funded to pay their deposits if it is
+/// signed.
+fn register_origin<T: Config>() -> T::Origin {
+	let origin = T::RegisterOrigin::successful_origin();
+	if let Ok(depositor) = ensure_signed(origin.clone()) {
+		T::Currency::make_free_balance_be(&depositor, DepositBalanceOf::<T>::max_value() / 2u32.into());
+	}
+	origin
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T::AssetId: From<u32>,
+			T::CustomMetadata: Default,
+	}
+
+	register_asset {
+		let metadata = metadata::<T>(1);
+	}: _(register_origin::<T>(), metadata.clone(), None)
+	verify {
+		let asset_id = Pallet::<T>::location_to_asset_id(MultiLocation::try_from(location(1)).unwrap()).unwrap();
+		assert_eq!(Pallet::<T>::metadata(asset_id), Some(metadata));
+	}
+
+	// skips the write to `LocationToAssetId`
+	register_asset_without_location {
+		let metadata = AssetMetadata { location: None, ..metadata::<T>(1) };
+	}: register_asset(register_origin::<T>(), metadata, None)
+
+	register_asset_batch {
+		let n in 1 .. 100;
+		let assets = (0..n).map(|i| (metadata::<T>(i), None)).collect::<Vec<_>>();
+	}: _(register_origin::<T>(), assets)
+	verify {
+		assert!(Pallet::<T>::location_to_asset_id(MultiLocation::try_from(location((n - 1).into())).unwrap()).is_some());
+	}
+
+	// the base weight of `update_asset`, without changing any field
+	update_asset {
+		let asset_id = register::<T>(1);
+	}: _(T::UpdateOrigin::successful_origin(), asset_id, None, None, None, None, None, None)
+
+	update_asset_decimals {
+		let asset_id = register::<T>(1);
+	}: update_asset(T::UpdateOrigin::successful_origin(), asset_id.clone(), Some(18), None, None, None, None, None)
+	verify {
+		assert_eq!(Pallet::<T>::metadata(asset_id).unwrap().decimals, 18);
+	}
+
+	update_asset_name {
+		let asset_id = register::<T>(1);
+		let name = bounded_string::<T>(2);
+	}: update_asset(T::UpdateOrigin::successful_origin(), asset_id.clone(), None, Some(name.to_vec()), None, None, None, None)
+	verify {
+		assert_eq!(Pallet::<T>::metadata(asset_id).unwrap().name, name);
+	}
+
+	// replaces the symbol in `SymbolToAssetId` if symbols are unique
+	update_asset_symbol {
+		let asset_id = register::<T>(1);
+		let symbol = bounded_string::<T>(2);
+	}: update_asset(T::UpdateOrigin::successful_origin(), asset_id.clone(), None, None, Some(symbol.to_vec()), None, None, None)
+	verify {
+		assert_eq!(Pallet::<T>::metadata(asset_id).unwrap().symbol, symbol);
+	}
+
+	update_asset_existential_deposit {
+		let asset_id = register::<T>(1);
+	}: update_asset(T::UpdateOrigin::successful_origin(), asset_id.clone(), None, None, None, Some(2u32.into()), None, None)
+	verify {
+		assert_eq!(Pallet::<T>::metadata(asset_id).unwrap().existential_deposit, 2u32.into());
+	}
+
+	// replaces an existing location, so both the old and the new reverse
+	// mapping are touched
+	update_asset_location {
+		let asset_id = register::<T>(1);
+	}: update_asset(T::UpdateOrigin::successful_origin(), asset_id.clone(), None, None, None, None, Some(Some(location(u128::MAX))), None)
+	verify {
+		assert_eq!(Pallet::<T>::location_to_asset_id(MultiLocation::try_from(location(u128::MAX)).unwrap()), Some(asset_id));
+	}
+
+	update_asset_additional {
+		let asset_id = register::<T>(1);
+	}: update_asset(T::UpdateOrigin::successful_origin(), asset_id, None, None, None, None, None, Some(Default::default()))
+
+	// replaces an existing location, so both the old and the new reverse
+	// mapping are touched
+	set_asset_location {
+		let asset_id = register::<T>(1);
+		let new_location = location(u128::MAX);
+	}: set_location(T::UpdateOrigin::successful_origin(), asset_id.clone(), Box::new(new_location.clone()))
+	verify {
+		assert_eq!(Pallet::<T>::location_to_asset_id(MultiLocation::try_from(new_location).unwrap()), Some(asset_id));
+	}
+
+	// takes the location away from another asset that holds it
+	force_set_location {
+		let asset_id = register::<T>(1);
+		let holder = register::<T>(2);
+	}: _(T::UpdateOrigin::successful_origin(), asset_id.clone(), Box::new(location(2)))
+	verify {
+		assert_eq!(Pallet::<T>::location_to_asset_id(MultiLocation::try_from(location(2)).unwrap()), Some(asset_id));
+		assert_eq!(Pallet::<T>::metadata(holder).unwrap().location, None);
+	}
+
+	// counts the `n` existing additional locations, up to the maximum
+	add_location {
+		let n in 0 .. T::MaxAdditionalLocations::get().saturating_sub(1);
+		let asset_id = register::<T>(1);
+		add_locations::<T>(&asset_id, 1, n);
+	}: _(T::UpdateOrigin::successful_origin(), asset_id.clone(), Box::new(location(u128::MAX)))
+	verify {
+		assert_eq!(Pallet::<T>::location_to_asset_id(MultiLocation::try_from(location(u128::MAX)).unwrap()), Some(asset_id));
+	}
+
+	remove_location {
+		let asset_id = register::<T>(1);
+		Pallet::<T>::do_add_location(asset_id.clone(), location(u128::MAX)).unwrap();
+	}: _(T::UpdateOrigin::successful_origin(), asset_id, Box::new(location(u128::MAX)))
+	verify {
+		assert_eq!(Pallet::<T>::location_to_asset_id(MultiLocation::try_from(location(u128::MAX)).unwrap()), None);
+	}
+
+	freeze_asset {
+		let asset_id = register::<T>(1);
+	}: _(T::UpdateOrigin::successful_origin(), asset_id.clone())
+	verify {
+		assert!(Pallet::<T>::is_frozen(&asset_id));
+	}
+
+	thaw_asset {
+		let asset_id = register::<T>(1);
+		Pallet::<T>::do_freeze_asset(asset_id.clone()).unwrap();
+	}: _(T::UpdateOrigin::successful_origin(), asset_id.clone())
+	verify {
+		assert!(!Pallet::<T>::is_frozen(&asset_id));
+	}
+
+	set_xcm_enabled {
+		let asset_id = register::<T>(1);
+	}: _(T::UpdateOrigin::successful_origin(), asset_id.clone(), false)
+	verify {
+		assert!(!Pallet::<T>::is_xcm_enabled(&asset_id));
+	}
+
+	// compares against `LargestAssetId`, which is set by the registration
+	set_last_asset_id {
+		let asset_id = register::<T>(1);
+	}: _(T::UpdateOrigin::successful_origin(), asset_id.clone())
+	verify {
+		assert_eq!(Pallet::<T>::last_asset_id(), asset_id);
+	}
+
+	set_native_relay_asset {
+		let asset_id = register::<T>(1);
+	}: _(T::UpdateOrigin::successful_origin(), asset_id.clone())
+	verify {
+		assert_eq!(Pallet::<T>::native_relay_asset_id(), Some(asset_id));
+	}
+
+	set_sufficient {
+		let asset_id = register::<T>(1);
+	}: _(T::UpdateOrigin::successful_origin(), asset_id.clone(), true)
+	verify {
+		assert!(Pallet::<T>::is_sufficient(&asset_id));
+	}
+
+	// both assets with a location and `n` additional locations, and one of
+	// them the native relay asset
+	swap_asset_ids {
+		let n in 0 .. T::MaxAdditionalLocations::get();
+		let a = register::<T>(1);
+		let b = register::<T>(2);
+		add_locations::<T>(&a, 0, n);
+		add_locations::<T>(&b, T::MaxAdditionalLocations::get().into(), n);
+		Pallet::<T>::do_set_native_relay_asset(a.clone()).unwrap();
+	}: _(T::UpdateOrigin::successful_origin(), a.clone(), b.clone())
+	verify {
+		assert_eq!(Pallet::<T>::location_to_asset_id(MultiLocation::try_from(location(1)).unwrap()), Some(b.clone()));
+		assert_eq!(Pallet::<T>::native_relay_asset_id(), Some(b));
+	}
+
+	// requires `AllowRegistrationWithDeposit`
+	register_asset_with_deposit {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T>::max_value() / 2u32.into());
+		let metadata = metadata::<T>(1);
+	}: _(RawOrigin::Signed(caller.clone()), metadata.clone())
+	verify {
+		let asset_id = Pallet::<T>::location_to_asset_id(MultiLocation::try_from(location(1)).unwrap()).unwrap();
+		assert_eq!(Pallet::<T>::deposits(&asset_id).map(|(depositor, _)| depositor), Some(caller));
+	}
+
+	// frozen, sufficient, the native relay asset, with a location and `n`
+	// additional locations to clean up, and retired afterwards
+	deregister_asset {
+		let n in 0 .. T::MaxAdditionalLocations::get();
+		let asset_id = register::<T>(1);
+		add_locations::<T>(&asset_id, 0, n);
+		Pallet::<T>::do_freeze_asset(asset_id.clone()).unwrap();
+		Pallet::<T>::do_set_sufficient(asset_id.clone(), true).unwrap();
+		Pallet::<T>::do_set_native_relay_asset(asset_id.clone()).unwrap();
+	}: _(T::UpdateOrigin::successful_origin(), asset_id.clone(), false)
+	verify {
+		assert!(Pallet::<T>::metadata(&asset_id).is_none());
+		assert!(Pallet::<T>::retired_asset_ids(&asset_id).is_some());
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::para_ext(1), crate::mock::para::Runtime);
@@ -1,23 +1,36 @@
 use crate::{module::*, AssetMetadata};
-use frame_support::{log, pallet_prelude::*, weights::constants::WEIGHT_PER_SECOND};
+use frame_support::{
+	log,
+	pallet_prelude::*,
+	traits::{
+		tokens::{fungibles, DepositConsequence, WithdrawConsequence},
+		Contains, GetDefault,
+	},
+	weights::constants::WEIGHT_PER_SECOND,
+};
 use orml_traits::{
-	asset_registry::{AssetProcessor, FixedConversionRateProvider, WeightToFeeConverter},
+	asset_registry::{AssetProcessor, FixedConversionRateProvider, Inspect, Mutate, WeightToFeeConverter},
 	GetByKey,
 };
 use sp_runtime::FixedPointNumber;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Bounded, CheckedAdd, One},
-	ArithmeticError, FixedU128,
+	traits::{AtLeast32BitUnsigned, Bounded, CheckedAdd, Convert, One},
+	FixedU128,
 };
 use sp_std::prelude::*;
-use xcm::v2::prelude::*;
+use xcm::{latest::prelude::*, VersionedMultiLocation};
 use xcm_builder::TakeRevenue;
 use xcm_executor::{traits::WeightTrader, Assets};
 
 /// Alias for AssetMetadata to improve readability (and to placate clippy)
-pub type DefaultAssetMetadata<T> = AssetMetadata<<T as Config>::Balance, <T as Config>::CustomMetadata>;
+pub type DefaultAssetMetadata<T> =
+	AssetMetadata<<T as Config>::Balance, <T as Config>::CustomMetadata, <T as Config>::StringLimit>;
 
 /// An AssetProcessor that assigns a sequential ID
+///
+/// Assets registered without an id get `LastAssetId + 1`. An explicit id is
+/// only accepted if it is that same next id. Registration fails once the id
+/// space is exhausted.
 pub struct SequentialId<T>(PhantomData<T>);
 
 impl<T> AssetProcessor<T::AssetId, DefaultAssetMetadata<T>> for SequentialId<T>
@@ -29,9 +42,10 @@ where
 		id: Option<T::AssetId>,
 		asset_metadata: DefaultAssetMetadata<T>,
 	) -> Result<(T::AssetId, DefaultAssetMetadata<T>), DispatchError> {
+		// the id space is exhausted, wrapping around would collide
 		let next_id = LastAssetId::<T>::get()
 			.checked_add(&T::AssetId::one())
-			.ok_or(ArithmeticError::Overflow)?;
+			.ok_or(Error::<T>::InvalidAssetId)?;
 
 		match id {
 			Some(explicit_id) if explicit_id != next_id => {
@@ -44,6 +58,68 @@ where
 			}
 		}
 	}
+
+	fn note_asset_id(id: &T::AssetId) {
+		// make sure explicitly chosen ids are never handed out again
+		LastAssetId::<T>::mutate(|last_asset_id| {
+			if *id > *last_asset_id {
+				*last_asset_id = id.clone();
+			}
+		});
+	}
+
+	fn try_state() -> Result<(), &'static str> {
+		let last_asset_id = LastAssetId::<T>::get();
+		ensure!(
+			Metadata::<T>::iter_keys().all(|asset_id| asset_id <= last_asset_id),
+			"An asset id is larger than LastAssetId"
+		);
+		Ok(())
+	}
+}
+
+/// An AssetProcessor like `SequentialId`, that keeps the ids in the inclusive
+/// range `Reserved` free, e.g. for system assets
+///
+/// Explicit ids in the reserved range are rejected with `InvalidAssetId`, and
+/// sequential ids skip over it. Assets in the range can be registered with
+/// `do_register_asset_without_asset_processor`. Like `SequentialId`, it can be
+/// used directly or be delegated to from a custom `AssetProcessor`.
+pub struct RangedSequentialId<T, Reserved>(PhantomData<(T, Reserved)>);
+
+impl<T, Reserved> AssetProcessor<T::AssetId, DefaultAssetMetadata<T>> for RangedSequentialId<T, Reserved>
+where
+	T: Config,
+	T::AssetId: AtLeast32BitUnsigned,
+	Reserved: Get<(T::AssetId, T::AssetId)>,
+{
+	fn pre_register(
+		id: Option<T::AssetId>,
+		asset_metadata: DefaultAssetMetadata<T>,
+	) -> Result<(T::AssetId, DefaultAssetMetadata<T>), DispatchError> {
+		let (first, last) = Reserved::get();
+		let is_reserved = |id: &T::AssetId| first <= *id && *id <= last;
+
+		if let Some(ref explicit_id) = id {
+			ensure!(!is_reserved(explicit_id), Error::<T>::InvalidAssetId);
+		}
+
+		// continue after the reserved range once the next id would be in it
+		let next_id = LastAssetId::<T>::get().checked_add(&T::AssetId::one());
+		if next_id.map_or(false, |next_id| is_reserved(&next_id)) {
+			LastAssetId::<T>::put(&last);
+		}
+
+		SequentialId::<T>::pre_register(id, asset_metadata)
+	}
+
+	fn note_asset_id(id: &T::AssetId) {
+		SequentialId::<T>::note_asset_id(id)
+	}
+
+	fn try_state() -> Result<(), &'static str> {
+		SequentialId::<T>::try_state()
+	}
 }
 
 /// A default implementation for WeightToFeeConverter that takes a fixed
@@ -58,6 +134,22 @@ impl<P: FixedConversionRateProvider> WeightToFeeConverter for FixedRateAssetRegi
 	}
 }
 
+/// A FixedConversionRateProvider that derives the fee per second from the
+/// metadata registered for a location. `F` extracts the rate from the
+/// metadata, typically from a field of `CustomMetadata`, and returns `None`
+/// if the asset can't be used to pay fees.
+pub struct MetadataConversionRateProvider<T, F>(PhantomData<(T, F)>);
+impl<T, F> FixedConversionRateProvider for MetadataConversionRateProvider<T, F>
+where
+	T: Config,
+	F: Convert<DefaultAssetMetadata<T>, Option<u128>>,
+{
+	fn get_fee_per_second(location: &MultiLocation) -> Option<u128> {
+		let metadata = Pallet::<T>::fetch_metadata_by_location(location)?;
+		F::convert(metadata)
+	}
+}
+
 /// Helper struct for the AssetRegistryTrader that stores the data about
 /// bought weight.
 pub struct BoughtWeight {
@@ -169,3 +261,250 @@ impl<T: Config> GetByKey<T::AssetId, T::Balance> for ExistentialDeposits<T> {
 		}
 	}
 }
+
+/// Like `ExistentialDeposits`, but returns `Fallback` for assets that are not
+/// registered, which defaults to `Balance::default()`.
+pub struct ExistentialDepositsFromRegistry<T, Fallback = GetDefault>(PhantomData<(T, Fallback)>);
+
+impl<T: Config, Fallback: Get<T::Balance>> GetByKey<T::AssetId, T::Balance>
+	for ExistentialDepositsFromRegistry<T, Fallback>
+{
+	fn get(k: &T::AssetId) -> T::Balance {
+		Pallet::<T>::metadata(k)
+			.map(|metadata| metadata.existential_deposit)
+			.unwrap_or_else(Fallback::get)
+	}
+}
+
+/// Contains the ids of registered assets. Can be used as a filter, e.g. for
+/// whitelisting fee assets.
+pub struct RegisteredAssets<T>(PhantomData<T>);
+
+impl<T: Config> Contains<T::AssetId> for RegisteredAssets<T> {
+	fn contains(asset_id: &T::AssetId) -> bool {
+		Pallet::<T>::asset_exists(asset_id)
+	}
+}
+
+/// Contains the locations that resolve to a registered asset, be it the
+/// location in its metadata or an additional location. Can be used as a
+/// filter in XCM barriers.
+pub struct RegisteredLocations<T>(PhantomData<T>);
+
+impl<T: Config> Contains<MultiLocation> for RegisteredLocations<T> {
+	fn contains(location: &MultiLocation) -> bool {
+		LocationToAssetId::<T>::contains_key(Pallet::<T>::canonicalize_location(location.clone()))
+	}
+}
+
+/// Like `RegisteredLocations`, but leaves out the locations of assets that
+/// have been disabled for XCM with `set_xcm_enabled`.
+pub struct XcmEnabledLocations<T>(PhantomData<T>);
+
+impl<T: Config> Contains<MultiLocation> for XcmEnabledLocations<T> {
+	fn contains(location: &MultiLocation) -> bool {
+		<Pallet<T> as Inspect>::asset_id(location).map_or(false, |asset_id| Pallet::<T>::is_xcm_enabled(&asset_id))
+	}
+}
+
+/// Contains the locations that resolve to a registered asset and are under
+/// one of the `Prefixes`, e.g. `(1, X1(Parachain(2000)))` to only allow
+/// assets of that sibling. Can be used to compose registry state into XCM
+/// barriers. See `Pallet::asset_ids_under` for when a location is under a
+/// prefix. As a filter of `MultiAsset`s, it contains the concrete assets
+/// with such a location.
+pub struct AllowRegisteredAssetsFrom<T, Prefixes>(PhantomData<(T, Prefixes)>);
+
+impl<T: Config, Prefixes: Get<Vec<MultiLocation>>> Contains<MultiLocation> for AllowRegisteredAssetsFrom<T, Prefixes> {
+	fn contains(location: &MultiLocation) -> bool {
+		let location = Pallet::<T>::canonicalize_location(location.clone());
+		Prefixes::get()
+			.into_iter()
+			.any(|prefix| Pallet::<T>::is_under(&location, &Pallet::<T>::canonicalize_location(prefix)))
+			&& LocationToAssetId::<T>::contains_key(&location)
+	}
+}
+
+impl<T: Config, Prefixes: Get<Vec<MultiLocation>>> Contains<MultiAsset> for AllowRegisteredAssetsFrom<T, Prefixes> {
+	fn contains(asset: &MultiAsset) -> bool {
+		match &asset.id {
+			Concrete(location) => <Self as Contains<MultiLocation>>::contains(location),
+			Abstract(_) => false,
+		}
+	}
+}
+
+/// Converts an asset id into the location stored in its metadata, if any.
+/// Like `LocationToAssetIdConvert`, the `xcm_executor` implementation
+/// converts in both directions and returns the input on failure.
+pub struct AssetIdToLocation<T>(PhantomData<T>);
+
+impl<T: Config> Convert<T::AssetId, Option<MultiLocation>> for AssetIdToLocation<T> {
+	fn convert(asset_id: T::AssetId) -> Option<MultiLocation> {
+		Pallet::<T>::multilocation(&asset_id).ok().flatten()
+	}
+}
+
+impl<T: Config> xcm_executor::traits::Convert<T::AssetId, MultiLocation> for AssetIdToLocation<T> {
+	fn convert(asset_id: T::AssetId) -> Result<MultiLocation, T::AssetId> {
+		<Self as Convert<_, _>>::convert(asset_id.clone()).ok_or(asset_id)
+	}
+
+	fn reverse(location: MultiLocation) -> Result<T::AssetId, MultiLocation> {
+		<Pallet<T> as Inspect>::asset_id(&location).ok_or(location)
+	}
+}
+
+/// Converts a location into the id of the asset it resolves to, if any. The
+/// `xcm_executor` implementation converts in both directions, so that it can
+/// be used as the asset id converter of `xcm_builder::FungiblesAdapter`.
+pub struct LocationToAssetIdConvert<T>(PhantomData<T>);
+
+impl<T: Config> Convert<MultiLocation, Option<T::AssetId>> for LocationToAssetIdConvert<T> {
+	fn convert(location: MultiLocation) -> Option<T::AssetId> {
+		<Pallet<T> as Inspect>::asset_id(&location)
+	}
+}
+
+impl<T: Config> xcm_executor::traits::Convert<MultiLocation, T::AssetId> for LocationToAssetIdConvert<T> {
+	fn convert(location: MultiLocation) -> Result<T::AssetId, MultiLocation> {
+		<Pallet<T> as Inspect>::asset_id(&location).ok_or(location)
+	}
+
+	fn reverse(asset_id: T::AssetId) -> Result<MultiLocation, T::AssetId> {
+		<AssetIdToLocation<T> as Convert<_, _>>::convert(asset_id.clone()).ok_or(asset_id)
+	}
+}
+
+impl<T: Config> Inspect for Pallet<T> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+	type CustomMetadata = T::CustomMetadata;
+	type StringLimit = T::StringLimit;
+
+	fn asset_id(location: &MultiLocation) -> Option<Self::AssetId> {
+		Pallet::<T>::fetch_asset_id_by_location(location)
+	}
+
+	fn metadata(id: &Self::AssetId) -> Option<DefaultAssetMetadata<T>> {
+		Pallet::<T>::metadata(id)
+	}
+
+	fn metadata_by_location(location: &MultiLocation) -> Option<DefaultAssetMetadata<T>> {
+		Pallet::<T>::fetch_metadata_by_location(location)
+	}
+
+	fn location(asset_id: &Self::AssetId) -> Result<Option<MultiLocation>, DispatchError> {
+		Pallet::<T>::multilocation(asset_id)
+	}
+
+	fn ensure_asset_exists(asset_id: &Self::AssetId) -> Result<DefaultAssetMetadata<T>, DispatchError> {
+		Pallet::<T>::try_get_metadata(asset_id)
+	}
+}
+
+impl<T: Config> Mutate for Pallet<T> {
+	fn register_asset(
+		metadata: DefaultAssetMetadata<T>,
+		asset_id: Option<Self::AssetId>,
+	) -> Result<Self::AssetId, DispatchError> {
+		Pallet::<T>::do_register_asset(metadata, asset_id)
+	}
+
+	fn update_asset(
+		asset_id: Self::AssetId,
+		decimals: Option<u32>,
+		name: Option<Vec<u8>>,
+		symbol: Option<Vec<u8>>,
+		existential_deposit: Option<Self::Balance>,
+		location: Option<Option<VersionedMultiLocation>>,
+		additional: Option<Self::CustomMetadata>,
+	) -> DispatchResult {
+		Pallet::<T>::do_update_asset(
+			asset_id,
+			decimals,
+			name,
+			symbol,
+			existential_deposit,
+			location,
+			additional,
+		)
+	}
+
+	fn mutate_additional(asset_id: Self::AssetId, f: impl FnOnce(&mut Self::CustomMetadata)) -> DispatchResult {
+		// `f` returns the complete new value, so it isn't merged with the old one
+		Pallet::<T>::do_replace_metadata(asset_id, |metadata| {
+			let mut new_metadata = metadata.clone();
+			f(&mut new_metadata.additional);
+			Ok(new_metadata)
+		})
+	}
+}
+
+/// Pairs the balances of a `fungibles` implementation `F` with the name,
+/// symbol and decimals stored in the registry, so that it can be used where
+/// `fungibles::InspectMetadata` is expected. Decimals that don't fit in a `u8`
+/// saturate at `u8::MAX`. Unknown assets have an empty name and symbol and
+/// zero decimals.
+pub struct FungiblesWithMetadata<T, F>(PhantomData<(T, F)>);
+
+impl<T, F, AccountId> fungibles::Inspect<AccountId> for FungiblesWithMetadata<T, F>
+where
+	T: Config,
+	F: fungibles::Inspect<AccountId, AssetId = T::AssetId>,
+{
+	type AssetId = F::AssetId;
+	type Balance = F::Balance;
+
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+		F::total_issuance(asset)
+	}
+
+	fn minimum_balance(asset: Self::AssetId) -> Self::Balance {
+		F::minimum_balance(asset)
+	}
+
+	fn balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance {
+		F::balance(asset, who)
+	}
+
+	fn reducible_balance(asset: Self::AssetId, who: &AccountId, keep_alive: bool) -> Self::Balance {
+		F::reducible_balance(asset, who, keep_alive)
+	}
+
+	fn can_deposit(asset: Self::AssetId, who: &AccountId, amount: Self::Balance, mint: bool) -> DepositConsequence {
+		F::can_deposit(asset, who, amount, mint)
+	}
+
+	fn can_withdraw(
+		asset: Self::AssetId,
+		who: &AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		F::can_withdraw(asset, who, amount)
+	}
+}
+
+impl<T, F, AccountId> fungibles::InspectMetadata<AccountId> for FungiblesWithMetadata<T, F>
+where
+	T: Config,
+	F: fungibles::Inspect<AccountId, AssetId = T::AssetId>,
+{
+	fn name(asset: &Self::AssetId) -> Vec<u8> {
+		Pallet::<T>::metadata(asset)
+			.map(|metadata| metadata.name.into_inner())
+			.unwrap_or_default()
+	}
+
+	fn symbol(asset: &Self::AssetId) -> Vec<u8> {
+		Pallet::<T>::metadata(asset)
+			.map(|metadata| metadata.symbol.into_inner())
+			.unwrap_or_default()
+	}
+
+	fn decimals(asset: &Self::AssetId) -> u8 {
+		Pallet::<T>::metadata(asset)
+			.map(|metadata| metadata.decimals.try_into().unwrap_or(u8::MAX))
+			.unwrap_or_default()
+	}
+}
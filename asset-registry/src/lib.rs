@@ -3,61 +3,189 @@
 // This is fixed in https://github.com/rust-lang/rust-clippy/issues/8321
 #![allow(clippy::large_enum_variant)]
 
-use frame_support::{pallet_prelude::*, traits::EnsureOrigin, transactional};
+use frame_support::{
+	log,
+	pallet_prelude::*,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{Currency, EnsureOrigin, ReservableCurrency},
+	transactional, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
 use frame_system::pallet_prelude::*;
-use orml_traits::asset_registry::AssetProcessor;
+use orml_traits::{
+	asset_registry::{AssetProcessor, LocationCanonicalizer, MergeAdditional},
+	location::{RelativeReserveProvider, Reserve},
+};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Member},
-	DispatchResult,
+	traits::{AtLeast32BitUnsigned, BadOrigin, CheckedMul, Member, Saturating, Zero},
+	ArithmeticError, DispatchResult,
 };
 use sp_std::prelude::*;
-use xcm::{v2::prelude::*, VersionedMultiLocation};
+use xcm::{latest::prelude::*, VersionedMultiLocation};
 
 pub use impls::*;
 pub use module::*;
+pub use orml_traits::asset_registry::AssetMetadata;
 pub use weights::WeightInfo;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod impls;
+pub mod migrations;
 mod mock;
 mod tests;
 mod weights;
 
-/// Data describing the asset properties.
-#[derive(scale_info::TypeInfo, Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
-pub struct AssetMetadata<Balance, CustomMetadata: Parameter + Member + TypeInfo> {
-	pub decimals: u32,
-	pub name: Vec<u8>,
-	pub symbol: Vec<u8>,
-	pub existential_deposit: Balance,
-	pub location: Option<VersionedMultiLocation>,
-	pub additional: CustomMetadata,
+/// The changes to apply to one asset with `update_assets`. The fields have
+/// the same meaning as the arguments of `update_asset`.
+#[derive(Encode, Decode, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct AssetUpdate<T: Config> {
+	pub asset_id: T::AssetId,
+	pub decimals: Option<u32>,
+	pub name: Option<Vec<u8>>,
+	pub symbol: Option<Vec<u8>>,
+	pub existential_deposit: Option<T::Balance>,
+	pub location: Option<Option<VersionedMultiLocation>>,
+	pub additional: Option<T::CustomMetadata>,
+}
+
+/// A field of the metadata of an asset, as reported by `AssetFieldUpdated`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum AssetField {
+	Decimals,
+	Name,
+	Symbol,
+	ExistentialDeposit,
+	Location,
+	Additional,
 }
 
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
+	pub type DepositBalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// Additional non-standard metadata to store for each asset
+		/// Additional non-standard metadata to store for each asset. It is
+		/// stored along with the rest of the metadata and part of the proof
+		/// of every update, so it should be kept small.
 		type CustomMetadata: Parameter + Member + TypeInfo;
 
 		/// The type used as a unique asset id,
-		type AssetId: Parameter + Member + Default + TypeInfo;
+		type AssetId: Parameter + Member + Default + PartialOrd + TypeInfo + MaybeSerializeDeserialize;
+
+		/// The origin that is allowed to register new assets. Signed origins
+		/// reserve a deposit for each asset they register. Origins rejected
+		/// by it fall back to `AuthorityOrigin`, so runtimes migrating from
+		/// `AuthorityOrigin` can set it to `frame_system::EnsureNever<()>`
+		/// until they move registration over.
+		type RegisterOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+		/// The origin that is allowed to update and otherwise manage assets,
+		/// e.g. their metadata, locations, freezing and deregistration.
+		/// Origins rejected by it fall back to `AuthorityOrigin`, so runtimes
+		/// migrating from `AuthorityOrigin` can set it to
+		/// `frame_system::EnsureNever<()>` until they move management over.
+		type UpdateOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
 
-		/// The origin that is allowed to manipulate metadata.
+		/// Deprecated alias that satisfies both `RegisterOrigin` and
+		/// `UpdateOrigin`. To migrate, set both of them to this origin and
+		/// this one to `frame_system::EnsureNever<()>`.
+		#[deprecated(note = "Use `RegisterOrigin` and `UpdateOrigin` instead")]
 		type AuthorityOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
 
 		/// A filter ran upon metadata registration that assigns an is and
 		/// potentially modifies the supplied metadata.
-		type AssetProcessor: AssetProcessor<Self::AssetId, AssetMetadata<Self::Balance, Self::CustomMetadata>>;
+		type AssetProcessor: AssetProcessor<
+			Self::AssetId,
+			AssetMetadata<Self::Balance, Self::CustomMetadata, Self::StringLimit>,
+		>;
+
+		/// Combines the stored custom metadata with the `additional` value of
+		/// an update. Use `()` to replace it.
+		type MergeAdditional: MergeAdditional<Self::CustomMetadata>;
 
 		/// The balance type.
 		type Balance: Parameter + Member + AtLeast32BitUnsigned + Default + Copy;
 
+		/// The maximum length of the name and symbol of an asset.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// Maps locations to the canonical form used as `LocationToAssetId`
+		/// keys. Use `()` to keep locations as they are.
+		type LocationCanonicalizer: LocationCanonicalizer;
+
+		/// Whether two assets may not share the same symbol. The
+		/// `SymbolToAssetId` index is only maintained while this is enabled,
+		/// so it should not be turned on after assets have been registered.
+		#[pallet::constant]
+		type EnforceUniqueSymbol: Get<bool>;
+
+		/// Whether `register_unknown_location` may register assets resolved by
+		/// `AssetProcessor::resolve_unknown_location`. Lookups such as
+		/// `fetch_metadata_by_location` never register them, they only return
+		/// the resolved metadata.
+		#[pallet::constant]
+		type AutoRegisterUnknownLocations: Get<bool>;
+
+		/// Whether updates emit an `AssetFieldUpdated` event for every changed
+		/// field, in addition to `UpdatedAsset`.
+		#[pallet::constant]
+		type EmitAssetFieldUpdates: Get<bool>;
+
+		/// Whether updates emit an `AssetUpdated` event with the metadata
+		/// before and after the update, in addition to `UpdatedAsset`.
+		#[pallet::constant]
+		type EmitPreviousMetadata: Get<bool>;
+
+		/// The currency in which registration deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The base deposit reserved for registering an asset with a signed
+		/// origin or `register_asset_with_deposit`. With a `DepositPerByte` of
+		/// zero, it is a flat deposit per asset.
+		#[pallet::constant]
+		type DepositBase: Get<DepositBalanceOf<Self>>;
+
+		/// The deposit reserved per byte of encoded metadata, in addition to
+		/// `DepositBase`.
+		#[pallet::constant]
+		type DepositPerByte: Get<DepositBalanceOf<Self>>;
+
+		/// The largest number of additional locations an asset can have,
+		/// which bounds the work of deregistering it.
+		#[pallet::constant]
+		type MaxAdditionalLocations: Get<u32>;
+
+		/// The largest number of decimals an asset can be registered or
+		/// updated with, e.g. 38, the number of digits of `u128::MAX`.
+		#[pallet::constant]
+		type MaxDecimals: Get<u32>;
+
+		/// Whether assets can be registered or updated with an existential
+		/// deposit of zero.
+		#[pallet::constant]
+		type AllowZeroExistentialDeposit: Get<bool>;
+
+		/// Whether an asset can be given the local location
+		/// `MultiLocation::here()`, which otherwise shadows the native token of
+		/// this chain in `LocationToAssetId`.
+		#[pallet::constant]
+		type AllowLocalLocation: Get<bool>;
+
+		/// Whether any signed account can register assets with
+		/// `register_asset_with_deposit`, next to `RegisterOrigin` registering
+		/// them with `register_asset`.
+		#[pallet::constant]
+		type AllowRegistrationWithDeposit: Get<bool>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -75,6 +203,26 @@ pub mod module {
 		ConflictingLocation,
 		/// Another asset was already register with this asset id.
 		ConflictingAssetId,
+		/// Name or symbol is too long.
+		BadMetadata,
+		/// The location is not an additional location of this asset.
+		LocationNotFound,
+		/// The asset already has `Config::MaxAdditionalLocations` additional
+		/// locations.
+		TooManyLocations,
+		/// Another asset was already registered with this symbol.
+		ConflictingSymbol,
+		/// The number of decimals exceeds `Config::MaxDecimals`.
+		InvalidDecimals,
+		/// The existential deposit is zero, which
+		/// `Config::AllowZeroExistentialDeposit` forbids.
+		ZeroExistentialDeposit,
+		/// The location is `MultiLocation::here()`, which
+		/// `Config::AllowLocalLocation` forbids.
+		LocalLocationNotAllowed,
+		/// `register_asset_with_deposit` is disabled by
+		/// `Config::AllowRegistrationWithDeposit`.
+		RegistrationWithDepositDisabled,
 	}
 
 	#[pallet::event]
@@ -82,23 +230,83 @@ pub mod module {
 	pub enum Event<T: Config> {
 		RegisteredAsset {
 			asset_id: T::AssetId,
-			metadata: AssetMetadata<T::Balance, T::CustomMetadata>,
+			metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
 		},
 		UpdatedAsset {
 			asset_id: T::AssetId,
-			metadata: AssetMetadata<T::Balance, T::CustomMetadata>,
+			metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
 		},
 		SetLocation {
 			asset_id: T::AssetId,
 			location: Box<VersionedMultiLocation>,
 		},
+		DeregisteredAsset {
+			asset_id: T::AssetId,
+		},
+		AddedLocation {
+			asset_id: T::AssetId,
+			location: Box<VersionedMultiLocation>,
+		},
+		RemovedLocation {
+			asset_id: T::AssetId,
+			location: Box<VersionedMultiLocation>,
+		},
+		FrozenAsset {
+			asset_id: T::AssetId,
+		},
+		ThawedAsset {
+			asset_id: T::AssetId,
+		},
+		SetLastAssetId {
+			asset_id: T::AssetId,
+		},
+		SetXcmEnabled {
+			asset_id: T::AssetId,
+			enabled: bool,
+		},
+		SetNativeRelayAsset {
+			asset_id: T::AssetId,
+		},
+		SetSufficient {
+			asset_id: T::AssetId,
+			sufficient: bool,
+		},
+		/// The deposit of an asset registered with a signed origin was
+		/// returned to its depositor, as the asset was deregistered. `amount`
+		/// is the part of the deposit that was still reserved.
+		DepositReleased {
+			asset_id: T::AssetId,
+			depositor: T::AccountId,
+			amount: DepositBalanceOf<T>,
+		},
+		/// A field of an asset was changed by an update. `old` and `new` are
+		/// the SCALE encoded values of the field. Only emitted if
+		/// `Config::EmitAssetFieldUpdates` is enabled.
+		AssetFieldUpdated {
+			asset_id: T::AssetId,
+			field: AssetField,
+			old: Vec<u8>,
+			new: Vec<u8>,
+		},
+		/// The metadata of an asset was updated from `old` to `new`. Only
+		/// emitted if `Config::EmitPreviousMetadata` is enabled.
+		AssetUpdated {
+			asset_id: T::AssetId,
+			old: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+			new: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+		},
 	}
 
 	/// The metadata of an asset, indexed by asset id.
 	#[pallet::storage]
 	#[pallet::getter(fn metadata)]
-	pub type Metadata<T: Config> =
-		StorageMap<_, Twox64Concat, T::AssetId, AssetMetadata<T::Balance, T::CustomMetadata>, OptionQuery>;
+	pub type Metadata<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AssetId,
+		AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+		OptionQuery,
+	>;
 
 	/// Maps a multilocation to an asset id - useful when processing xcm
 	/// messages.
@@ -106,54 +314,177 @@ pub mod module {
 	#[pallet::getter(fn location_to_asset_id)]
 	pub type LocationToAssetId<T: Config> = StorageMap<_, Twox64Concat, MultiLocation, T::AssetId, OptionQuery>;
 
+	/// Maps a symbol to an asset id. Only maintained if
+	/// `Config::EnforceUniqueSymbol` is enabled.
+	#[pallet::storage]
+	#[pallet::getter(fn symbol_to_asset_id)]
+	pub type SymbolToAssetId<T: Config> =
+		StorageMap<_, Twox64Concat, BoundedVec<u8, T::StringLimit>, T::AssetId, OptionQuery>;
+
+	/// Additional locations of an asset, besides the one in its metadata.
+	/// Each of them is also present in `LocationToAssetId`.
+	#[pallet::storage]
+	#[pallet::getter(fn additional_locations)]
+	pub type AdditionalLocations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AssetId, Twox64Concat, MultiLocation, (), OptionQuery>;
+
+	/// Ids of deregistered assets that may not be registered again.
+	///
+	/// Deregistering an asset retires its id unless it is explicitly marked
+	/// as reusable. Reusing an id never rewinds `LastAssetId`, so
+	/// `SequentialId` does not hand it out again; it can only be taken by
+	/// registering with that explicit id.
+	#[pallet::storage]
+	#[pallet::getter(fn retired_asset_ids)]
+	pub type RetiredAssetIds<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, (), OptionQuery>;
+
+	/// Assets that governance has frozen. The registry does not enforce this
+	/// itself, it is up to other pallets to check `is_frozen`.
+	#[pallet::storage]
+	pub type FrozenAssets<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, (), OptionQuery>;
+
+	/// Assets that may be used locally but not over XCM. Their locations are
+	/// left out of `XcmEnabledLocations`.
+	#[pallet::storage]
+	pub type XcmDisabledAssets<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, (), OptionQuery>;
+
+	/// Assets that can sustain an account on their own, like sufficient
+	/// assets in `pallet-assets`. The registry does not enforce this itself,
+	/// it is up to other pallets, e.g. the tokens layer deciding whether to
+	/// require a provider reference, to check `is_sufficient`.
+	#[pallet::storage]
+	pub type SufficientAssets<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, (), OptionQuery>;
+
+	/// The block an asset was registered at and the block its metadata was
+	/// last updated at with `update_asset`, `set_location` or
+	/// `force_set_location`. Assets registered before this was introduced
+	/// have no entry until they are updated.
+	#[pallet::storage]
+	pub type AssetTimestamps<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, (T::BlockNumber, T::BlockNumber), OptionQuery>;
+
+	/// The asset of the relay chain's native token, if set with
+	/// `set_native_relay_asset`. Lets hot paths such as fee payment find it
+	/// without reconstructing its location. Cleared when the asset is
+	/// deregistered.
+	#[pallet::storage]
+	#[pallet::getter(fn native_relay_asset_id)]
+	pub type NativeRelayAssetId<T: Config> = StorageValue<_, T::AssetId, OptionQuery>;
+
+	/// The account that registered an asset with a signed origin and the
+	/// deposit reserved from it. The deposit follows the size of the metadata
+	/// and is returned when the asset is deregistered.
+	#[pallet::storage]
+	#[pallet::getter(fn deposits)]
+	pub type Deposits<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, (T::AccountId, DepositBalanceOf<T>), OptionQuery>;
+
 	/// The last processed asset id - used when assigning a sequential id.
+	///
+	/// `SequentialId` advances it past any larger id that is registered
+	/// explicitly, and it can be set with `set_last_asset_id`.
 	#[pallet::storage]
 	#[pallet::getter(fn last_asset_id)]
 	pub(crate) type LastAssetId<T: Config> = StorageValue<_, T::AssetId, ValueQuery>;
 
+	/// The largest id any asset has been registered with. It is not lowered
+	/// when assets are deregistered, as their ids may be registered again.
+	/// Chains with assets registered before it was introduced fill it in with
+	/// `migrations::SetLargestAssetId`.
+	#[pallet::storage]
+	#[pallet::getter(fn largest_asset_id)]
+	pub type LargestAssetId<T: Config> = StorageValue<_, T::AssetId, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
-		_phantom: PhantomData<T>,
+		/// Assets to register at genesis, given as an optional asset id and
+		/// the SCALE-encoded `AssetMetadata`. They are registered through
+		/// the `AssetProcessor`, so e.g. `SequentialId` keeps `LastAssetId`
+		/// in sync.
+		pub assets: Vec<(Option<T::AssetId>, Vec<u8>)>,
+		/// The initial `LastAssetId`, set before `assets` are registered.
+		pub last_asset_id: T::AssetId,
 	}
 
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self {
-				_phantom: Default::default(),
+			GenesisConfig {
+				assets: vec![],
+				last_asset_id: Default::default(),
 			}
 		}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
-		fn build(&self) {}
+		fn build(&self) {
+			LastAssetId::<T>::put(&self.last_asset_id);
+
+			self.assets.iter().for_each(|(asset_id, metadata)| {
+				let metadata = AssetMetadata::decode(&mut &metadata[..])
+					.expect("asset metadata in genesis should be SCALE-encoded");
+				Pallet::<T>::do_register_asset(metadata, asset_id.clone())
+					.expect("assets in genesis should not conflict");
+			});
+		}
 	}
 
+	/// The current storage version. Bump it when changing the layout of
+	/// stored data and add a migration to `migrations`.
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
 
+	// Call indices are assigned in declaration order and are part of the
+	// encoding of transactions, so calls must never be reordered or removed.
+	// New calls are appended at the end.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(T::WeightInfo::register_asset())]
+		#[pallet::weight(Pallet::<T>::register_asset_weight(metadata.location.is_some()))]
 		#[transactional]
 		pub fn register_asset(
 			origin: OriginFor<T>,
-			metadata: AssetMetadata<T::Balance, T::CustomMetadata>,
+			metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
 			asset_id: Option<T::AssetId>,
 		) -> DispatchResult {
-			T::AuthorityOrigin::ensure_origin(origin)?;
+			let depositor = ensure_signed(origin.clone()).ok();
+			Self::ensure_register_origin(origin)?;
 
-			Self::do_register_asset(metadata, asset_id)
+			let asset_id = Self::do_register_asset(metadata, asset_id)?;
+			if let Some(depositor) = depositor {
+				Self::do_reserve_deposit(asset_id, depositor)?;
+			}
+
+			Ok(())
 		}
 
+		/// Update the metadata of an asset. Fields that are `None` are left
+		/// unchanged, any other value replaces the stored one. This is also
+		/// how fields are cleared: `name` and `symbol` by passing an empty
+		/// string, and `location` by passing `Some(None)`. Empty symbols are
+		/// exempt from `EnforceUniqueSymbol`.
 		#[allow(clippy::too_many_arguments)]
-		#[pallet::weight(T::WeightInfo::update_asset())]
+		#[pallet::weight(Pallet::<T>::update_asset_weight(
+			decimals.is_some(),
+			name.is_some(),
+			symbol.is_some(),
+			existential_deposit.is_some(),
+			location.is_some(),
+			additional.is_some(),
+		))]
 		#[transactional]
 		pub fn update_asset(
 			origin: OriginFor<T>,
@@ -165,7 +496,7 @@ pub mod module {
 			location: Option<Option<VersionedMultiLocation>>,
 			additional: Option<T::CustomMetadata>,
 		) -> DispatchResult {
-			T::AuthorityOrigin::ensure_origin(origin)?;
+			Self::ensure_update_origin(origin)?;
 
 			Self::do_update_asset(
 				asset_id,
@@ -179,35 +510,265 @@ pub mod module {
 
 			Ok(())
 		}
+
+		#[pallet::weight(T::WeightInfo::deregister_asset(T::MaxAdditionalLocations::get()))]
+		#[transactional]
+		pub fn deregister_asset(origin: OriginFor<T>, asset_id: T::AssetId, reusable: bool) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_deregister_asset(asset_id, reusable)
+		}
+
+		#[pallet::weight(T::WeightInfo::set_asset_location())]
+		#[transactional]
+		pub fn set_location(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			location: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_set_location(asset_id, *location)
+		}
+
+		#[pallet::weight(T::WeightInfo::add_location(T::MaxAdditionalLocations::get()))]
+		#[transactional]
+		pub fn add_location(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			location: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_add_location(asset_id, *location)
+		}
+
+		#[pallet::weight(T::WeightInfo::remove_location())]
+		#[transactional]
+		pub fn remove_location(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			location: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_remove_location(asset_id, *location)
+		}
+
+		#[pallet::weight(T::WeightInfo::force_set_location())]
+		#[transactional]
+		pub fn force_set_location(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			location: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_force_set_location(asset_id, *location)
+		}
+
+		#[pallet::weight(T::WeightInfo::freeze_asset())]
+		pub fn freeze_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_freeze_asset(asset_id)
+		}
+
+		#[pallet::weight(T::WeightInfo::thaw_asset())]
+		pub fn thaw_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_thaw_asset(asset_id)
+		}
+
+		/// Set the id the `AssetProcessor` continues from. It can't be below
+		/// `LargestAssetId`.
+		#[pallet::weight(T::WeightInfo::set_last_asset_id())]
+		pub fn set_last_asset_id(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+			ensure!(
+				LargestAssetId::<T>::get().map_or(true, |largest| largest <= asset_id),
+				Error::<T>::InvalidAssetId
+			);
+
+			LastAssetId::<T>::put(&asset_id);
+			Self::deposit_event(Event::<T>::SetLastAssetId { asset_id });
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::register_asset_batch(assets.len() as u32))]
+		#[transactional]
+		pub fn register_asset_batch(
+			origin: OriginFor<T>,
+			assets: Vec<(
+				AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+				Option<T::AssetId>,
+			)>,
+		) -> DispatchResult {
+			let depositor = ensure_signed(origin.clone()).ok();
+			Self::ensure_register_origin(origin)?;
+
+			for (metadata, asset_id) in assets {
+				let asset_id = Self::do_register_asset(metadata, asset_id)?;
+				if let Some(ref depositor) = depositor {
+					Self::do_reserve_deposit(asset_id, depositor.clone())?;
+				}
+			}
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_xcm_enabled())]
+		pub fn set_xcm_enabled(origin: OriginFor<T>, asset_id: T::AssetId, enabled: bool) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_set_xcm_enabled(asset_id, enabled)
+		}
+
+		#[pallet::weight(Pallet::<T>::update_assets_weight(updates))]
+		#[transactional]
+		pub fn update_assets(origin: OriginFor<T>, updates: Vec<AssetUpdate<T>>) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			for update in updates {
+				Self::do_update_asset(
+					update.asset_id,
+					update.decimals,
+					update.name,
+					update.symbol,
+					update.existential_deposit,
+					update.location,
+					update.additional,
+				)?;
+			}
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_native_relay_asset())]
+		pub fn set_native_relay_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_set_native_relay_asset(asset_id)
+		}
+
+		#[pallet::weight(T::WeightInfo::set_sufficient())]
+		pub fn set_sufficient(origin: OriginFor<T>, asset_id: T::AssetId, sufficient: bool) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_set_sufficient(asset_id, sufficient)
+		}
+
+		#[pallet::weight(T::WeightInfo::swap_asset_ids(T::MaxAdditionalLocations::get()))]
+		#[transactional]
+		pub fn swap_asset_ids(origin: OriginFor<T>, a: T::AssetId, b: T::AssetId) -> DispatchResult {
+			Self::ensure_update_origin(origin)?;
+
+			Self::do_swap_asset_ids(a, b)
+		}
+
+		/// Register an asset as any signed account, reserving the same
+		/// deposit from it as `register_asset` does for signed origins. The
+		/// id is assigned by the `AssetProcessor`.
+		#[pallet::weight(T::WeightInfo::register_asset_with_deposit())]
+		#[transactional]
+		pub fn register_asset_with_deposit(
+			origin: OriginFor<T>,
+			metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+		) -> DispatchResult {
+			let depositor = ensure_signed(origin)?;
+			ensure!(
+				T::AllowRegistrationWithDeposit::get(),
+				Error::<T>::RegistrationWithDepositDisabled
+			);
+
+			let asset_id = Self::do_register_asset(metadata, None)?;
+			Self::do_reserve_deposit(asset_id, depositor)
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	/// Register a new asset
+	/// Ensure `origin` is `RegisterOrigin`, falling back to the deprecated
+	/// `AuthorityOrigin`.
+	#[allow(deprecated)]
+	fn ensure_register_origin(origin: OriginFor<T>) -> DispatchResult {
+		T::RegisterOrigin::try_origin(origin)
+			.map(|_| ())
+			.or_else(|origin| T::AuthorityOrigin::try_origin(origin).map(|_| ()))
+			.map_err(|_| BadOrigin.into())
+	}
+
+	/// Ensure `origin` is `UpdateOrigin`, falling back to the deprecated
+	/// `AuthorityOrigin`.
+	#[allow(deprecated)]
+	fn ensure_update_origin(origin: OriginFor<T>) -> DispatchResult {
+		T::UpdateOrigin::try_origin(origin)
+			.map(|_| ())
+			.or_else(|origin| T::AuthorityOrigin::try_origin(origin).map(|_| ()))
+			.map_err(|_| BadOrigin.into())
+	}
+
+	/// Register a new asset, returning the id assigned by the
+	/// `AssetProcessor`
 	pub fn do_register_asset(
-		metadata: AssetMetadata<T::Balance, T::CustomMetadata>,
+		metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
 		asset_id: Option<T::AssetId>,
-	) -> DispatchResult {
+	) -> Result<T::AssetId, DispatchError> {
 		let (asset_id, metadata) = T::AssetProcessor::pre_register(asset_id, metadata)?;
 
 		Self::do_register_asset_without_asset_processor(metadata.clone(), asset_id.clone())?;
 
-		T::AssetProcessor::post_register(asset_id, metadata)?;
+		T::AssetProcessor::post_register(asset_id.clone(), metadata)?;
 
-		Ok(())
+		Ok(asset_id)
+	}
+
+	/// Return the id of an existing asset matching `metadata` and
+	/// `asset_id`, or register a new one like `do_register_asset`. An asset
+	/// matches if it has the explicitly given id, or if its location equals
+	/// the location in `metadata`. Lets bootstrap tooling register assets
+	/// idempotently; the metadata of existing assets is left as it is.
+	pub fn get_or_register(
+		metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+		asset_id: Option<T::AssetId>,
+	) -> Result<T::AssetId, DispatchError> {
+		if let Some(asset_id) = asset_id.as_ref().filter(|asset_id| Self::asset_exists(asset_id)) {
+			return Ok(asset_id.clone());
+		}
+		if let Some(existing) = metadata
+			.location
+			.as_ref()
+			.and_then(Self::fetch_asset_id_by_versioned_location)
+		{
+			return Ok(existing);
+		}
+
+		Self::do_register_asset(metadata, asset_id)
 	}
 
 	/// Like do_register_asset, but without calling pre_register and
-	/// post_register hooks.
+	/// post_register hooks. Returns the given asset id.
 	/// This function is useful in tests but it might also come in useful to
 	/// users.
 	pub fn do_register_asset_without_asset_processor(
-		metadata: AssetMetadata<T::Balance, T::CustomMetadata>,
+		metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
 		asset_id: T::AssetId,
-	) -> DispatchResult {
+	) -> Result<T::AssetId, DispatchError> {
+		ensure!(metadata.decimals <= T::MaxDecimals::get(), Error::<T>::InvalidDecimals);
+		ensure!(
+			T::AllowZeroExistentialDeposit::get() || !metadata.existential_deposit.is_zero(),
+			Error::<T>::ZeroExistentialDeposit
+		);
+
 		Metadata::<T>::try_mutate(&asset_id, |maybe_metadata| -> DispatchResult {
 			// make sure this asset id has not been registered yet
 			ensure!(maybe_metadata.is_none(), Error::<T>::ConflictingAssetId);
+			ensure!(
+				!RetiredAssetIds::<T>::contains_key(&asset_id),
+				Error::<T>::ConflictingAssetId
+			);
 
 			*maybe_metadata = Some(metadata.clone());
 
@@ -215,12 +776,22 @@ impl<T: Config> Pallet<T> {
 				Self::do_insert_location(asset_id.clone(), location.clone())?;
 			}
 
+			Self::do_insert_symbol(asset_id.clone(), metadata.symbol.clone())?;
+			T::AssetProcessor::note_asset_id(&asset_id);
+			Self::note_largest_asset_id(&asset_id);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			AssetTimestamps::<T>::insert(&asset_id, (now, now));
+
 			Ok(())
 		})?;
 
-		Self::deposit_event(Event::<T>::RegisteredAsset { asset_id, metadata });
+		Self::deposit_event(Event::<T>::RegisteredAsset {
+			asset_id: asset_id.clone(),
+			metadata,
+		});
 
-		Ok(())
+		Ok(asset_id)
 	}
 
 	pub fn do_update_asset(
@@ -232,49 +803,713 @@ impl<T: Config> Pallet<T> {
 		location: Option<Option<VersionedMultiLocation>>,
 		additional: Option<T::CustomMetadata>,
 	) -> DispatchResult {
-		Metadata::<T>::try_mutate(&asset_id, |maybe_metadata| -> DispatchResult {
-			let metadata = maybe_metadata.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+		Self::do_replace_metadata(asset_id, |metadata| {
+			let mut new_metadata = metadata.clone();
 			if let Some(decimals) = decimals {
-				metadata.decimals = decimals;
+				new_metadata.decimals = decimals;
 			}
 
 			if let Some(name) = name {
-				metadata.name = name;
+				new_metadata.name = Self::bounded_string(name)?;
 			}
 
 			if let Some(symbol) = symbol {
-				metadata.symbol = symbol;
+				new_metadata.symbol = Self::bounded_string(symbol)?;
 			}
 
 			if let Some(existential_deposit) = existential_deposit {
-				metadata.existential_deposit = existential_deposit;
+				new_metadata.existential_deposit = existential_deposit;
 			}
 
 			if let Some(location) = location {
-				Self::do_update_location(asset_id.clone(), metadata.location.clone(), location.clone())?;
-				metadata.location = location;
+				new_metadata.location = location;
 			}
 
 			if let Some(additional) = additional {
-				metadata.additional = additional;
+				new_metadata.additional = T::MergeAdditional::merge_additional(&metadata.additional, additional);
 			}
 
+			Ok(new_metadata)
+		})
+	}
+
+	/// Replace the metadata of an existing asset as a whole, e.g. from a
+	/// migration. Like `do_update_asset`, this maintains the location and
+	/// symbol mappings and emits `UpdatedAsset`, but `additional` is
+	/// replaced without going through `Config::MergeAdditional`.
+	pub fn do_set_metadata(
+		asset_id: T::AssetId,
+		metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+	) -> DispatchResult {
+		Self::do_replace_metadata(asset_id, |_| Ok(metadata))
+	}
+
+	/// replace the metadata of an existing asset with the one returned by
+	/// `f`, which is passed the current metadata
+	fn do_replace_metadata(
+		asset_id: T::AssetId,
+		f: impl FnOnce(
+			&AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+		) -> Result<AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>, DispatchError>,
+	) -> DispatchResult {
+		let metadata = Metadata::<T>::try_mutate(&asset_id, |maybe_metadata| -> Result<_, DispatchError> {
+			let metadata = maybe_metadata.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+
+			let new_metadata = f(metadata)?;
+			let new_metadata = T::AssetProcessor::pre_update(&asset_id, metadata, new_metadata)?;
+
+			// assets registered before a limit was introduced keep their values
+			if new_metadata.decimals != metadata.decimals {
+				ensure!(
+					new_metadata.decimals <= T::MaxDecimals::get(),
+					Error::<T>::InvalidDecimals
+				);
+			}
+			if new_metadata.existential_deposit != metadata.existential_deposit {
+				ensure!(
+					T::AllowZeroExistentialDeposit::get() || !new_metadata.existential_deposit.is_zero(),
+					Error::<T>::ZeroExistentialDeposit
+				);
+			}
+
+			if new_metadata.symbol != metadata.symbol {
+				Self::do_remove_symbol(&metadata.symbol);
+				Self::do_insert_symbol(asset_id.clone(), new_metadata.symbol.clone())?;
+			}
+
+			Self::do_update_location(
+				asset_id.clone(),
+				metadata.location.clone(),
+				new_metadata.location.clone(),
+			)?;
+
+			if T::EmitAssetFieldUpdates::get() {
+				Self::deposit_field_updates(&asset_id, metadata, &new_metadata);
+			}
+
+			let old_metadata = sp_std::mem::replace(metadata, new_metadata);
+			Self::note_updated(&asset_id);
+
 			Self::deposit_event(Event::<T>::UpdatedAsset {
 				asset_id: asset_id.clone(),
 				metadata: metadata.clone(),
 			});
+			if T::EmitPreviousMetadata::get() {
+				Self::deposit_event(Event::<T>::AssetUpdated {
+					asset_id: asset_id.clone(),
+					old: old_metadata,
+					new: metadata.clone(),
+				});
+			}
 
-			Ok(())
+			Ok(metadata.clone())
 		})?;
 
+		T::AssetProcessor::post_update(&asset_id, &metadata)?;
+		Self::do_update_deposit(&asset_id, &metadata)?;
+
 		Ok(())
 	}
 
+	/// emit `AssetFieldUpdated` for every field that differs between `old`
+	/// and `new`
+	fn deposit_field_updates(
+		asset_id: &T::AssetId,
+		old: &AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+		new: &AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+	) {
+		let deposit = |field, old: Vec<u8>, new: Vec<u8>| {
+			if old != new {
+				Self::deposit_event(Event::<T>::AssetFieldUpdated {
+					asset_id: asset_id.clone(),
+					field,
+					old,
+					new,
+				});
+			}
+		};
+
+		deposit(AssetField::Decimals, old.decimals.encode(), new.decimals.encode());
+		deposit(AssetField::Name, old.name.encode(), new.name.encode());
+		deposit(AssetField::Symbol, old.symbol.encode(), new.symbol.encode());
+		deposit(
+			AssetField::ExistentialDeposit,
+			old.existential_deposit.encode(),
+			new.existential_deposit.encode(),
+		);
+		deposit(AssetField::Location, old.location.encode(), new.location.encode());
+		deposit(AssetField::Additional, old.additional.encode(), new.additional.encode());
+	}
+
+	/// Update only the location of an existing asset
+	pub fn do_set_location(asset_id: T::AssetId, location: VersionedMultiLocation) -> DispatchResult {
+		Self::do_replace_metadata(asset_id.clone(), |metadata| {
+			let mut new_metadata = metadata.clone();
+			new_metadata.location = Some(location.clone());
+			Ok(new_metadata)
+		})?;
+
+		Self::deposit_event(Event::<T>::SetLocation {
+			asset_id,
+			location: Box::new(location),
+		});
+
+		Ok(())
+	}
+
+	/// Like `do_set_location`, but takes the location away from any asset
+	/// that currently holds it instead of failing with `ConflictingLocation`
+	pub fn do_force_set_location(asset_id: T::AssetId, location: VersionedMultiLocation) -> DispatchResult {
+		let location_key = Self::location_key(location.clone())?;
+		Self::ensure_asset_exists(&asset_id)?;
+
+		match LocationToAssetId::<T>::get(&location_key) {
+			Some(holder) if holder != asset_id => Self::do_detach_location(holder, &location_key)?,
+			Some(_) => {
+				// an additional location of this asset becomes its main location
+				if AdditionalLocations::<T>::take(&asset_id, &location_key).is_some() {
+					LocationToAssetId::<T>::remove(&location_key);
+				}
+			}
+			None => {}
+		}
+
+		Self::do_set_location(asset_id, location)
+	}
+
+	/// Add an additional location that resolves to an existing asset
+	pub fn do_add_location(asset_id: T::AssetId, location: VersionedMultiLocation) -> DispatchResult {
+		ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
+		ensure!(
+			(AdditionalLocations::<T>::iter_key_prefix(&asset_id).count() as u32) < T::MaxAdditionalLocations::get(),
+			Error::<T>::TooManyLocations
+		);
+
+		Self::do_insert_location(asset_id.clone(), location.clone())?;
+		AdditionalLocations::<T>::insert(&asset_id, Self::location_key(location.clone())?, ());
+
+		Self::deposit_event(Event::<T>::AddedLocation {
+			asset_id,
+			location: Box::new(location),
+		});
+
+		Ok(())
+	}
+
+	/// Remove an additional location of an asset. The location stored in the
+	/// metadata can only be changed through `set_location` or `update_asset`.
+	pub fn do_remove_location(asset_id: T::AssetId, location: VersionedMultiLocation) -> DispatchResult {
+		let location_key = Self::location_key(location.clone())?;
+		AdditionalLocations::<T>::take(&asset_id, &location_key).ok_or(Error::<T>::LocationNotFound)?;
+		LocationToAssetId::<T>::remove(location_key);
+
+		Self::deposit_event(Event::<T>::RemovedLocation {
+			asset_id,
+			location: Box::new(location),
+		});
+
+		Ok(())
+	}
+
+	/// Mark an asset as frozen
+	pub fn do_freeze_asset(asset_id: T::AssetId) -> DispatchResult {
+		ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
+
+		FrozenAssets::<T>::insert(&asset_id, ());
+		Self::deposit_event(Event::<T>::FrozenAsset { asset_id });
+
+		Ok(())
+	}
+
+	/// Clear the frozen mark of an asset
+	pub fn do_thaw_asset(asset_id: T::AssetId) -> DispatchResult {
+		ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
+
+		FrozenAssets::<T>::remove(&asset_id);
+		Self::deposit_event(Event::<T>::ThawedAsset { asset_id });
+
+		Ok(())
+	}
+
+	/// Whether the asset has been frozen by governance. Unregistered assets
+	/// are not frozen.
+	pub fn is_frozen(asset_id: &T::AssetId) -> bool {
+		FrozenAssets::<T>::contains_key(asset_id)
+	}
+
+	/// Mark a registered asset as the native token of the relay chain
+	pub fn do_set_native_relay_asset(asset_id: T::AssetId) -> DispatchResult {
+		ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
+
+		NativeRelayAssetId::<T>::put(&asset_id);
+		Self::deposit_event(Event::<T>::SetNativeRelayAsset { asset_id });
+
+		Ok(())
+	}
+
+	/// Allow or disallow the use of an asset over XCM
+	pub fn do_set_xcm_enabled(asset_id: T::AssetId, enabled: bool) -> DispatchResult {
+		ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
+
+		if enabled {
+			XcmDisabledAssets::<T>::remove(&asset_id);
+		} else {
+			XcmDisabledAssets::<T>::insert(&asset_id, ());
+		}
+		Self::deposit_event(Event::<T>::SetXcmEnabled { asset_id, enabled });
+
+		Ok(())
+	}
+
+	/// Whether the asset may be used over XCM. Assets are enabled unless
+	/// disabled with `set_xcm_enabled`.
+	pub fn is_xcm_enabled(asset_id: &T::AssetId) -> bool {
+		!XcmDisabledAssets::<T>::contains_key(asset_id)
+	}
+
+	/// Mark an asset as sufficient or not
+	pub fn do_set_sufficient(asset_id: T::AssetId, sufficient: bool) -> DispatchResult {
+		ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
+
+		if sufficient {
+			SufficientAssets::<T>::insert(&asset_id, ());
+		} else {
+			SufficientAssets::<T>::remove(&asset_id);
+		}
+		Self::deposit_event(Event::<T>::SetSufficient { asset_id, sufficient });
+
+		Ok(())
+	}
+
+	/// Whether the asset can sustain an account on its own. Assets are not
+	/// sufficient unless marked so with `set_sufficient`.
+	pub fn is_sufficient(asset_id: &T::AssetId) -> bool {
+		SufficientAssets::<T>::contains_key(asset_id)
+	}
+
+	/// Swap the ids of two assets, along with their locations, flags and
+	/// deposits. Balances of the assets held by other pallets are keyed by
+	/// asset id and are not swapped.
+	pub fn do_swap_asset_ids(a: T::AssetId, b: T::AssetId) -> DispatchResult {
+		let metadata_a = Self::try_get_metadata(&a)?;
+		let metadata_b = Self::try_get_metadata(&b)?;
+		if a == b {
+			return Ok(());
+		}
+
+		Metadata::<T>::swap(&a, &b);
+		FrozenAssets::<T>::swap(&a, &b);
+		AssetTimestamps::<T>::swap(&a, &b);
+		XcmDisabledAssets::<T>::swap(&a, &b);
+		SufficientAssets::<T>::swap(&a, &b);
+		Deposits::<T>::swap(&a, &b);
+		NativeRelayAssetId::<T>::mutate(|maybe_asset_id| {
+			if maybe_asset_id.as_ref() == Some(&a) {
+				*maybe_asset_id = Some(b.clone());
+			} else if maybe_asset_id.as_ref() == Some(&b) {
+				*maybe_asset_id = Some(a.clone());
+			}
+		});
+
+		// drain both before reinserting, as each asset takes over the prefix
+		// of the other
+		let additional_a: Vec<_> = AdditionalLocations::<T>::drain_prefix(&a)
+			.map(|(location, ())| location)
+			.collect();
+		let additional_b: Vec<_> = AdditionalLocations::<T>::drain_prefix(&b)
+			.map(|(location, ())| location)
+			.collect();
+		Self::do_repoint_asset(&metadata_a, additional_a, &a, &b)?;
+		Self::do_repoint_asset(&metadata_b, additional_b, &b, &a)?;
+
+		Self::deposit_event(Event::<T>::UpdatedAsset {
+			asset_id: b,
+			metadata: metadata_a,
+		});
+		Self::deposit_event(Event::<T>::UpdatedAsset {
+			asset_id: a,
+			metadata: metadata_b,
+		});
+
+		Ok(())
+	}
+
+	/// Remove an asset and its location mappings, if any. Unless `reusable`
+	/// is set, the asset id can't be registered again.
+	pub fn do_deregister_asset(asset_id: T::AssetId, reusable: bool) -> DispatchResult {
+		let metadata = Self::try_get_metadata(&asset_id)?;
+		// convert the location before the first write, so that a location of
+		// a bad version leaves the asset untouched
+		let location = match metadata.location {
+			Some(location) => Some((Self::location_key(location.clone())?, location)),
+			None => None,
+		};
+		Metadata::<T>::remove(&asset_id);
+
+		if !reusable {
+			RetiredAssetIds::<T>::insert(&asset_id, ());
+		}
+
+		Self::do_remove_symbol(&metadata.symbol);
+		FrozenAssets::<T>::remove(&asset_id);
+		XcmDisabledAssets::<T>::remove(&asset_id);
+		SufficientAssets::<T>::remove(&asset_id);
+		AssetTimestamps::<T>::remove(&asset_id);
+		if NativeRelayAssetId::<T>::get().as_ref() == Some(&asset_id) {
+			NativeRelayAssetId::<T>::kill();
+		}
+		if let Some((depositor, deposit)) = Deposits::<T>::take(&asset_id) {
+			let remainder = T::Currency::unreserve(&depositor, deposit);
+			Self::deposit_event(Event::<T>::DepositReleased {
+				asset_id: asset_id.clone(),
+				depositor,
+				amount: deposit.saturating_sub(remainder),
+			});
+		}
+
+		if let Some((location_key, location)) = location {
+			LocationToAssetId::<T>::remove(location_key);
+			Self::deposit_event(Event::<T>::RemovedLocation {
+				asset_id: asset_id.clone(),
+				location: Box::new(location),
+			});
+		}
+		AdditionalLocations::<T>::drain_prefix(&asset_id).for_each(|(location, ())| {
+			LocationToAssetId::<T>::remove(&location);
+			Self::deposit_event(Event::<T>::RemovedLocation {
+				asset_id: asset_id.clone(),
+				location: Box::new(location.into()),
+			});
+		});
+
+		Self::deposit_event(Event::<T>::DeregisteredAsset { asset_id });
+
+		Ok(())
+	}
+
+	/// The weight of `register_asset`, which saves the write to
+	/// `LocationToAssetId` for assets without a location
+	pub fn register_asset_weight(has_location: bool) -> Weight {
+		if has_location {
+			T::WeightInfo::register_asset()
+		} else {
+			T::WeightInfo::register_asset_without_location()
+		}
+	}
+
+	/// The weight of `update_asset`: a base weight plus the weight of each
+	/// field that is changed
+	pub fn update_asset_weight(
+		decimals: bool,
+		name: bool,
+		symbol: bool,
+		existential_deposit: bool,
+		location: bool,
+		additional: bool,
+	) -> Weight {
+		[
+			(decimals, T::WeightInfo::update_asset_decimals()),
+			(name, T::WeightInfo::update_asset_name()),
+			(symbol, T::WeightInfo::update_asset_symbol()),
+			(existential_deposit, T::WeightInfo::update_asset_existential_deposit()),
+			(location, T::WeightInfo::update_asset_location()),
+			(additional, T::WeightInfo::update_asset_additional()),
+		]
+		.into_iter()
+		.filter(|(changed, _)| *changed)
+		.fold(T::WeightInfo::update_asset(), |weight, (_, field_weight)| {
+			weight.saturating_add(field_weight)
+		})
+	}
+
+	/// The weight of `update_assets`: the sum of the weights of the
+	/// individual updates
+	pub fn update_assets_weight(updates: &[AssetUpdate<T>]) -> Weight {
+		updates.iter().fold(0, |weight, update| {
+			weight.saturating_add(Self::update_asset_weight(
+				update.decimals.is_some(),
+				update.name.is_some(),
+				update.symbol.is_some(),
+				update.existential_deposit.is_some(),
+				update.location.is_some(),
+				update.additional.is_some(),
+			))
+		})
+	}
+
+	/// The asset registered with the given symbol. If
+	/// `Config::EnforceUniqueSymbol` is enabled, this is a lookup in
+	/// `SymbolToAssetId`. Otherwise several assets may share the symbol; all
+	/// assets are scanned and the lowest matching id is returned.
+	pub fn asset_id_by_symbol(symbol: &[u8]) -> Option<T::AssetId>
+	where
+		T::AssetId: Ord,
+	{
+		if T::EnforceUniqueSymbol::get() {
+			let symbol: BoundedVec<u8, T::StringLimit> = symbol.to_vec().try_into().ok()?;
+			SymbolToAssetId::<T>::get(symbol)
+		} else {
+			Metadata::<T>::iter()
+				.filter(|(_, metadata)| metadata.symbol[..] == *symbol)
+				.map(|(asset_id, _)| asset_id)
+				.min()
+		}
+	}
+
+	/// The ids of all assets with a location under `prefix`, i.e. with the
+	/// same number of parents and an interior that starts with the junctions
+	/// of `prefix`. A location is under itself. Assets with several locations
+	/// under `prefix` are returned once per location.
+	pub fn asset_ids_under(prefix: &MultiLocation) -> Vec<T::AssetId> {
+		let prefix = Self::canonicalize_location(prefix.clone());
+		LocationToAssetId::<T>::iter()
+			.filter(|(location, _)| Self::is_under(location, &prefix))
+			.map(|(_, asset_id)| asset_id)
+			.collect()
+	}
+
+	/// whether `location` has the same number of parents as `prefix` and an
+	/// interior that starts with the junctions of `prefix`
+	pub(crate) fn is_under(location: &MultiLocation, prefix: &MultiLocation) -> bool {
+		location.parents == prefix.parents
+			&& location.interior.len() >= prefix.interior.len()
+			&& prefix
+				.interior
+				.iter()
+				.zip(location.interior.iter())
+				.all(|(a, b)| a == b)
+	}
+
+	/// Lazily iterate over all registered assets and their metadata
+	pub fn assets() -> impl Iterator<Item = (T::AssetId, AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>)>
+	{
+		Metadata::<T>::iter()
+	}
+
+	/// Return up to `limit` assets in storage order, starting after
+	/// `start_key` if given
+	pub fn assets_paginated(
+		start_key: Option<T::AssetId>,
+		limit: u32,
+	) -> Vec<(T::AssetId, AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>)> {
+		let iter = match start_key {
+			Some(start_key) => Metadata::<T>::iter_from(Metadata::<T>::hashed_key_for(start_key)),
+			None => Metadata::<T>::iter(),
+		};
+		iter.take(limit as usize).collect()
+	}
+
+	/// Like `assets_paginated`, but also returns the `start_key` of the next
+	/// page, or `None` if this is the last page. A `limit` of 0 returns an
+	/// empty page and `start_key` itself.
+	pub fn metadata_paged(
+		start_key: Option<T::AssetId>,
+		limit: u32,
+	) -> (
+		Vec<(T::AssetId, AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>)>,
+		Option<T::AssetId>,
+	) {
+		if limit == 0 {
+			return (Vec::new(), start_key);
+		}
+
+		let mut page = Self::assets_paginated(start_key, limit.saturating_add(1));
+		if page.len() <= limit as usize {
+			return (page, None);
+		}
+
+		page.truncate(limit as usize);
+		let next_key = page.last().map(|(asset_id, _)| asset_id.clone());
+		(page, next_key)
+	}
+
+	/// Fetch the metadata of each of the given assets, if registered
+	pub fn get_metadata(
+		asset_ids: &[T::AssetId],
+	) -> Vec<(
+		T::AssetId,
+		Option<AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>>,
+	)> {
+		asset_ids
+			.iter()
+			.map(|asset_id| (asset_id.clone(), Metadata::<T>::get(asset_id)))
+			.collect()
+	}
+
+	/// Apply `f` to the metadata of an asset, if registered, e.g. to read a
+	/// single field of the custom metadata without cloning the metadata.
+	///
+	/// Example:
+	/// ```ignore
+	/// let fee_per_second = orml_asset_registry::Pallet::<T>::with_metadata(&asset_id, |metadata| {
+	/// 	metadata.additional.fee_per_second
+	/// });
+	/// ```
+	pub fn with_metadata<R>(
+		asset_id: &T::AssetId,
+		f: impl FnOnce(&AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>) -> R,
+	) -> Option<R> {
+		Metadata::<T>::get(asset_id).map(|metadata| f(&metadata))
+	}
+
+	/// The decimals of an asset, if registered
+	pub fn decimals(asset_id: &T::AssetId) -> Option<u32> {
+		Metadata::<T>::get(asset_id).map(|metadata| metadata.decimals)
+	}
+
+	/// The name of an asset, if registered
+	pub fn name(asset_id: &T::AssetId) -> Option<Vec<u8>> {
+		Metadata::<T>::get(asset_id).map(|metadata| metadata.name.into_inner())
+	}
+
+	/// The symbol of an asset, if registered
+	pub fn symbol(asset_id: &T::AssetId) -> Option<Vec<u8>> {
+		Metadata::<T>::get(asset_id).map(|metadata| metadata.symbol.into_inner())
+	}
+
+	/// The form of `location` that is used as key of `LocationToAssetId`, as
+	/// determined by `Config::LocationCanonicalizer`. Locations with the same
+	/// canonical form resolve to the same asset and conflict on registration.
+	pub fn canonicalize_location(location: MultiLocation) -> MultiLocation {
+		T::LocationCanonicalizer::canonicalize(location)
+	}
+
+	/// Convert an amount of asset `from` into the same value expressed in the
+	/// decimals of asset `to`. Scaling down rounds towards zero, scaling up
+	/// fails with `ArithmeticError::Overflow` if the result doesn't fit.
+	pub fn convert_amount(from: &T::AssetId, to: &T::AssetId, amount: T::Balance) -> Result<T::Balance, DispatchError> {
+		let to_decimals = Self::decimals(to).ok_or(Error::<T>::AssetNotFound)?;
+		Self::rescale_amount(from, amount, to_decimals)
+	}
+
+	/// Like `convert_amount`, but converts into an arbitrary number of
+	/// decimals, e.g. those of the same asset on another chain.
+	pub fn rescale_amount(
+		asset_id: &T::AssetId,
+		amount: T::Balance,
+		to_decimals: u32,
+	) -> Result<T::Balance, DispatchError> {
+		let from_decimals = Self::decimals(asset_id).ok_or(Error::<T>::AssetNotFound)?;
+		let ten = T::Balance::from(10u32);
+
+		let mut amount = amount;
+		if to_decimals >= from_decimals {
+			for _ in from_decimals..to_decimals {
+				if amount.is_zero() {
+					break;
+				}
+				amount = amount.checked_mul(&ten).ok_or(ArithmeticError::Overflow)?;
+			}
+		} else {
+			for _ in to_decimals..from_decimals {
+				if amount.is_zero() {
+					break;
+				}
+				amount = amount / ten;
+			}
+		}
+
+		Ok(amount)
+	}
+
+	/// The block an asset was registered at, if it is registered and was
+	/// registered since registration blocks are tracked
+	pub fn registered_at(asset_id: &T::AssetId) -> Option<T::BlockNumber> {
+		AssetTimestamps::<T>::get(asset_id).map(|(registered_at, _)| registered_at)
+	}
+
+	/// The block the metadata of an asset was last updated at, or the block
+	/// it was registered at if it was never updated
+	pub fn updated_at(asset_id: &T::AssetId) -> Option<T::BlockNumber> {
+		AssetTimestamps::<T>::get(asset_id).map(|(_, updated_at)| updated_at)
+	}
+
+	/// Whether an asset is registered with this id, without decoding its
+	/// metadata.
+	pub fn asset_exists(asset_id: &T::AssetId) -> bool {
+		Metadata::<T>::contains_key(asset_id)
+	}
+
+	/// Whether an asset is registered for `location`, as its primary or an
+	/// additional location, without decoding the asset id.
+	pub fn location_exists(location: &MultiLocation) -> bool {
+		LocationToAssetId::<T>::contains_key(Self::canonicalize_location(location.clone()))
+	}
+
+	/// The id of the asset registered for `location`, if any.
+	pub fn fetch_asset_id_by_location(location: &MultiLocation) -> Option<T::AssetId> {
+		LocationToAssetId::<T>::get(Self::canonicalize_location(location.clone()))
+	}
+
+	/// Like `fetch_asset_id_by_location`, but for a versioned location.
+	pub fn fetch_asset_id_by_versioned_location(location: &VersionedMultiLocation) -> Option<T::AssetId> {
+		let location = Self::latest_location(location.clone()).ok()?;
+		Self::fetch_asset_id_by_location(&location)
+	}
+
+	/// The metadata of the asset registered for `location`. Unknown locations
+	/// are passed to `AssetProcessor::resolve_unknown_location`.
 	pub fn fetch_metadata_by_location(
 		location: &MultiLocation,
-	) -> Option<AssetMetadata<T::Balance, T::CustomMetadata>> {
-		let asset_id = LocationToAssetId::<T>::get(location)?;
-		Metadata::<T>::get(asset_id)
+	) -> Option<AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>> {
+		let location = Self::canonicalize_location(location.clone());
+		match LocationToAssetId::<T>::get(&location) {
+			Some(asset_id) => Metadata::<T>::get(asset_id),
+			None => T::AssetProcessor::resolve_unknown_location(&location).map(|(_, metadata)| metadata),
+		}
+	}
+
+	/// The id of the asset registered for `location`, registering the asset
+	/// resolved for it by `AssetProcessor::resolve_unknown_location` if
+	/// there is none and `AutoRegisterUnknownLocations` is enabled, e.g. for
+	/// an XCM asset transactor receiving a derivative asset for the first
+	/// time. Fails with `AssetNotFound` if no asset can be registered. The
+	/// caller has to account for at most `WeightInfo::register_asset`.
+	pub fn register_unknown_location(location: &MultiLocation) -> Result<T::AssetId, DispatchError> {
+		let location = Self::canonicalize_location(location.clone());
+		if let Some(asset_id) = LocationToAssetId::<T>::get(&location) {
+			return Ok(asset_id);
+		}
+		ensure!(T::AutoRegisterUnknownLocations::get(), Error::<T>::AssetNotFound);
+
+		let (asset_id, metadata) =
+			T::AssetProcessor::resolve_unknown_location(&location).ok_or(Error::<T>::AssetNotFound)?;
+		with_transaction(
+			|| match Self::do_register_asset_without_asset_processor(metadata, asset_id) {
+				Ok(asset_id) => TransactionOutcome::Commit(Ok(asset_id)),
+				Err(error) => TransactionOutcome::Rollback(Err(error)),
+			},
+		)
+	}
+
+	/// Like `fetch_metadata_by_location`, but for a versioned location.
+	/// Returns `None` both for unknown locations and for locations that
+	/// can't be converted to the latest XCM version, as neither can belong
+	/// to a registered asset.
+	pub fn fetch_metadata_by_versioned_location(
+		location: &VersionedMultiLocation,
+	) -> Option<AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>> {
+		let location = Self::latest_location(location.clone()).ok()?;
+		Self::fetch_metadata_by_location(&location)
+	}
+
+	/// The metadata of an asset, or `AssetNotFound` if it isn't registered.
+	///
+	/// Example:
+	/// ```ignore
+	/// let metadata = orml_asset_registry::Pallet::<T>::try_get_metadata(&asset_id)?;
+	/// ```
+	pub fn try_get_metadata(
+		asset_id: &T::AssetId,
+	) -> Result<AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>, DispatchError> {
+		Metadata::<T>::get(asset_id).ok_or_else(|| Error::<T>::AssetNotFound.into())
+	}
+
+	/// Same as `try_get_metadata`
+	pub fn ensure_asset_exists(
+		asset_id: &T::AssetId,
+	) -> Result<AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>, DispatchError> {
+		Self::try_get_metadata(asset_id)
 	}
 
 	pub fn multilocation(asset_id: &T::AssetId) -> Result<Option<MultiLocation>, DispatchError> {
@@ -282,11 +1517,114 @@ impl<T: Config> Pallet<T> {
 			.and_then(|metadata| {
 				metadata
 					.location
-					.map(|location| location.try_into().map_err(|()| Error::<T>::BadVersion.into()))
+					.map(|location| Self::latest_location(location).map_err(Into::into))
 			})
 			.transpose()
 	}
 
+	/// The reserve chain of an asset, derived from its location like
+	/// `orml_traits::location::RelativeReserveProvider` does: the parent or
+	/// the sibling or child parachain the location is under, or `Here` for
+	/// local assets. Assumes that assets are reserved on the chain their
+	/// location points into. `None` for assets without a location.
+	pub fn reserve_location(asset_id: &T::AssetId) -> Option<MultiLocation> {
+		let location = Self::multilocation(asset_id).ok().flatten()?;
+		RelativeReserveProvider::reserve(&(location, 0u128).into())
+	}
+
+	/// Whether `origin` is the reserve chain of an asset, as returned by
+	/// `reserve_location`, e.g. to decide if reserve transfers of the asset
+	/// from `origin` can be trusted.
+	pub fn is_reserve_asset(asset_id: &T::AssetId, origin: &MultiLocation) -> bool {
+		Self::reserve_location(asset_id).as_ref() == Some(origin)
+	}
+
+	/// Check that `LocationToAssetId` and `NativeRelayAssetId` are consistent
+	/// with the stored metadata and that the `AssetProcessor` state is valid.
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		for (location, asset_id) in LocationToAssetId::<T>::iter() {
+			let metadata = Metadata::<T>::get(&asset_id).ok_or("LocationToAssetId points to an unknown asset")?;
+			let is_canonical = match metadata.location {
+				Some(metadata_location) => Self::location_key(metadata_location).ok() == Some(location.clone()),
+				None => false,
+			};
+			ensure!(
+				is_canonical || AdditionalLocations::<T>::contains_key(&asset_id, &location),
+				"LocationToAssetId points to an asset without this location"
+			);
+		}
+
+		for (asset_id, metadata) in Metadata::<T>::iter() {
+			if let Some(location) = metadata.location {
+				let location = Self::location_key(location).map_err(|_| "Asset has a location of a bad version")?;
+				ensure!(
+					LocationToAssetId::<T>::get(location) == Some(asset_id),
+					"Asset location is missing from LocationToAssetId"
+				);
+			}
+		}
+
+		for (asset_id, location, ()) in AdditionalLocations::<T>::iter() {
+			ensure!(
+				LocationToAssetId::<T>::get(location) == Some(asset_id),
+				"Additional location is missing from LocationToAssetId"
+			);
+		}
+
+		let largest_asset_id = LargestAssetId::<T>::get();
+		ensure!(
+			Metadata::<T>::iter_keys()
+				.all(|asset_id| largest_asset_id.as_ref().map_or(false, |largest| asset_id <= *largest)),
+			"An asset id is larger than LargestAssetId"
+		);
+
+		if let Some(asset_id) = NativeRelayAssetId::<T>::get() {
+			ensure!(
+				Metadata::<T>::contains_key(asset_id),
+				"NativeRelayAssetId points to an unknown asset"
+			);
+		}
+
+		T::AssetProcessor::try_state()
+	}
+
+	/// The deposit for an asset with the given metadata
+	pub fn deposit_for(metadata: &AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>) -> DepositBalanceOf<T> {
+		let bytes: DepositBalanceOf<T> = (metadata.encoded_size() as u32).into();
+		T::DepositBase::get().saturating_add(T::DepositPerByte::get().saturating_mul(bytes))
+	}
+
+	/// reserve the deposit for a newly registered asset from `depositor`
+	fn do_reserve_deposit(asset_id: T::AssetId, depositor: T::AccountId) -> DispatchResult {
+		let metadata = Self::try_get_metadata(&asset_id)?;
+		let deposit = Self::deposit_for(&metadata);
+		T::Currency::reserve(&depositor, deposit)?;
+		Deposits::<T>::insert(&asset_id, (depositor, deposit));
+
+		Ok(())
+	}
+
+	/// reserve more or return some of the deposit of an asset after its
+	/// metadata changed, if it has a deposit
+	fn do_update_deposit(
+		asset_id: &T::AssetId,
+		metadata: &AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+	) -> DispatchResult {
+		Deposits::<T>::try_mutate(asset_id, |maybe_deposit| -> DispatchResult {
+			if let Some((depositor, deposit)) = maybe_deposit {
+				let new_deposit = Self::deposit_for(metadata);
+				if new_deposit > *deposit {
+					T::Currency::reserve(depositor, new_deposit.saturating_sub(*deposit))?;
+				} else {
+					T::Currency::unreserve(depositor, deposit.saturating_sub(new_deposit));
+				}
+				*deposit = new_deposit;
+			}
+			Ok(())
+		})
+	}
+
 	/// update LocationToAssetId mapping if the location changed
 	fn do_update_location(
 		asset_id: T::AssetId,
@@ -296,9 +1634,12 @@ impl<T: Config> Pallet<T> {
 		// Update `LocationToAssetId` only if location changed
 		if new_location != old_location {
 			// remove the old location lookup if it exists
-			if let Some(ref old_location) = old_location {
-				let location: MultiLocation = old_location.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
-				LocationToAssetId::<T>::remove(location);
+			if let Some(old_location) = old_location {
+				LocationToAssetId::<T>::remove(Self::location_key(old_location.clone())?);
+				Self::deposit_event(Event::<T>::RemovedLocation {
+					asset_id: asset_id.clone(),
+					location: Box::new(old_location),
+				});
 			}
 
 			// insert new location
@@ -310,14 +1651,141 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// remove a location from the asset holding it, be it the location in its
+	/// metadata or an additional location
+	fn do_detach_location(holder: T::AssetId, location: &MultiLocation) -> DispatchResult {
+		let holder_location = Metadata::<T>::get(&holder)
+			.and_then(|metadata| metadata.location)
+			.and_then(|location| Self::location_key(location).ok());
+		if holder_location.as_ref() == Some(location) {
+			// also removes the location from `LocationToAssetId`
+			return Self::do_replace_metadata(holder, |metadata| {
+				let mut new_metadata = metadata.clone();
+				new_metadata.location = None;
+				Ok(new_metadata)
+			});
+		}
+
+		LocationToAssetId::<T>::remove(location);
+		AdditionalLocations::<T>::remove(&holder, location);
+		Self::deposit_event(Event::<T>::RemovedLocation {
+			asset_id: holder,
+			location: Box::new(location.clone().into()),
+		});
+
+		Ok(())
+	}
+
+	/// raise `LargestAssetId` to `asset_id` if it is larger
+	pub(crate) fn note_largest_asset_id(asset_id: &T::AssetId) {
+		LargestAssetId::<T>::mutate(|largest| {
+			if largest.as_ref().map_or(true, |largest| asset_id > largest) {
+				*largest = Some(asset_id.clone());
+			}
+		});
+	}
+
+	/// record that the metadata of an asset was updated in the current
+	/// block. Assets registered before timestamps were tracked take the
+	/// current block as their registration block.
+	fn note_updated(asset_id: &T::AssetId) {
+		let now = frame_system::Pallet::<T>::block_number();
+		AssetTimestamps::<T>::mutate(asset_id, |timestamps| {
+			let registered_at = timestamps.map_or(now, |(registered_at, _)| registered_at);
+			*timestamps = Some((registered_at, now));
+		});
+	}
+
+	/// point the locations and symbol of an asset that moved from id `from`
+	/// to id `to` at its new id
+	fn do_repoint_asset(
+		metadata: &AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+		additional_locations: Vec<MultiLocation>,
+		from: &T::AssetId,
+		to: &T::AssetId,
+	) -> DispatchResult {
+		if let Some(location) = metadata.location.clone() {
+			LocationToAssetId::<T>::insert(Self::location_key(location)?, to);
+		}
+
+		for location in additional_locations {
+			LocationToAssetId::<T>::insert(&location, to);
+			AdditionalLocations::<T>::insert(to, location, ());
+		}
+
+		if T::EnforceUniqueSymbol::get() {
+			SymbolToAssetId::<T>::mutate(&metadata.symbol, |maybe_asset_id| {
+				if maybe_asset_id.as_ref() == Some(from) {
+					*maybe_asset_id = Some(to.clone());
+				}
+			});
+		}
+
+		Ok(())
+	}
+
 	/// insert location into the LocationToAssetId map
 	fn do_insert_location(asset_id: T::AssetId, location: VersionedMultiLocation) -> DispatchResult {
 		// if the metadata contains a location, set the LocationToAssetId
-		let location: MultiLocation = location.try_into().map_err(|()| Error::<T>::BadVersion)?;
+		let location = Self::location_key(location)?;
+		ensure!(
+			T::AllowLocalLocation::get() || location != MultiLocation::here(),
+			Error::<T>::LocalLocationNotAllowed
+		);
 		LocationToAssetId::<T>::try_mutate(&location, |maybe_asset_id| {
 			ensure!(maybe_asset_id.is_none(), Error::<T>::ConflictingLocation);
 			*maybe_asset_id = Some(asset_id);
 			Ok(())
 		})
 	}
+
+	/// insert symbol into the SymbolToAssetId map, if symbols are unique and
+	/// it is not empty
+	fn do_insert_symbol(asset_id: T::AssetId, symbol: BoundedVec<u8, T::StringLimit>) -> DispatchResult {
+		if !T::EnforceUniqueSymbol::get() || symbol.is_empty() {
+			return Ok(());
+		}
+
+		SymbolToAssetId::<T>::try_mutate(&symbol, |maybe_asset_id| {
+			ensure!(maybe_asset_id.is_none(), Error::<T>::ConflictingSymbol);
+			*maybe_asset_id = Some(asset_id);
+			Ok(())
+		})
+	}
+
+	/// remove symbol from the SymbolToAssetId map, if symbols are unique
+	fn do_remove_symbol(symbol: &BoundedVec<u8, T::StringLimit>) {
+		if T::EnforceUniqueSymbol::get() {
+			SymbolToAssetId::<T>::remove(symbol);
+		}
+	}
+
+	/// convert a versioned location into the latest `MultiLocation` version,
+	/// logging the version of locations that can't be converted since
+	/// `BadVersion` doesn't carry it
+	fn latest_location(location: VersionedMultiLocation) -> Result<MultiLocation, Error<T>> {
+		let version = match location {
+			VersionedMultiLocation::V0(_) => 0,
+			VersionedMultiLocation::V1(_) => 1,
+		};
+		location.try_into().map_err(|()| {
+			log::warn!(
+				target: "asset-registry",
+				"can't convert location of XCM version {} to the latest version",
+				version
+			);
+			Error::<T>::BadVersion
+		})
+	}
+
+	/// convert a versioned location into the canonical `MultiLocation` used
+	/// as `LocationToAssetId` key
+	fn location_key(location: VersionedMultiLocation) -> Result<MultiLocation, Error<T>> {
+		Self::latest_location(location).map(Self::canonicalize_location)
+	}
+
+	/// convert a name or symbol into its bounded representation
+	fn bounded_string(string: Vec<u8>) -> Result<BoundedVec<u8, T::StringLimit>, Error<T>> {
+		string.try_into().map_err(|_| Error::<T>::BadMetadata)
+	}
 }
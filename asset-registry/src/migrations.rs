@@ -0,0 +1,277 @@
+use crate::{AdditionalLocations, AssetMetadata, Config, LocationToAssetId, Metadata, Pallet};
+use codec::Decode;
+use frame_support::{log, pallet_prelude::*, sp_io::KillStorageResult, traits::OnRuntimeUpgrade};
+use sp_std::prelude::*;
+use xcm::{latest::MultiLocation, VersionedMultiLocation};
+
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+/// The layout of `AssetMetadata` before `name` and `symbol` were bounded.
+#[derive(Decode)]
+struct OldAssetMetadata<Balance, CustomMetadata> {
+	decimals: u32,
+	name: Vec<u8>,
+	symbol: Vec<u8>,
+	existential_deposit: Balance,
+	location: Option<VersionedMultiLocation>,
+	additional: CustomMetadata,
+}
+
+/// Migrates the stored metadata to bounded `name` and `symbol` fields, which
+/// is storage version 1.
+///
+/// The encoding of `Vec<u8>` and `BoundedVec<u8, _>` is identical, so entries
+/// are carried over as-is, except for names and symbols exceeding
+/// `T::StringLimit`, which are truncated to fit. Does nothing if the on-chain
+/// storage version is already 1 or higher.
+pub struct BoundNameAndSymbol<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for BoundNameAndSymbol<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = StorageVersion::get::<Pallet<T>>();
+		if on_chain_version >= 1 {
+			log::info!(
+				target: "asset-registry",
+				"BoundNameAndSymbol: skipping, storage version is {:?}",
+				on_chain_version
+			);
+			return T::DbWeight::get().reads(1);
+		}
+
+		let limit = T::StringLimit::get() as usize;
+		let truncate = |mut string: Vec<u8>| -> BoundedVec<u8, T::StringLimit> {
+			string.truncate(limit);
+			string.try_into().unwrap_or_default()
+		};
+
+		let mut count: u64 = 0;
+		Metadata::<T>::translate::<OldAssetMetadata<T::Balance, T::CustomMetadata>, _>(|_, old| {
+			count += 1;
+			Some(AssetMetadata {
+				decimals: old.decimals,
+				name: truncate(old.name),
+				symbol: truncate(old.symbol),
+				existential_deposit: old.existential_deposit,
+				location: old.location,
+				additional: old.additional,
+			})
+		});
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		Self::set_temp_storage(Metadata::<T>::iter_keys().count() as u32, "assets");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		let assets: u32 = Self::get_temp_storage("assets").ok_or("missing asset count")?;
+		ensure!(
+			StorageVersion::get::<Pallet<T>>() >= 1,
+			"storage version was not updated"
+		);
+		ensure!(
+			Metadata::<T>::iter_values().count() as u32 == assets,
+			"Metadata entries were lost"
+		);
+		Pallet::<T>::do_try_state()
+	}
+}
+
+/// Clears `LocationToAssetId` and rebuilds it from the locations in the
+/// metadata and `AdditionalLocations`, e.g. to recover from an index that
+/// drifted from the metadata. Returns the consumed weight, so it can be called
+/// from a runtime's `OnRuntimeUpgrade`; `RebuildLocationIndex` does just that.
+///
+/// Locations that can't be converted to the latest XCM version are left out.
+/// If several assets claim the same location, the first one visited keeps
+/// it and it is removed from the metadata or additional locations of the
+/// others. Both are logged instead of failing the upgrade.
+pub fn rebuild_location_index<T: Config>() -> Weight {
+	let removed = match LocationToAssetId::<T>::remove_all(None) {
+		KillStorageResult::AllRemoved(n) | KillStorageResult::SomeRemaining(n) => n as u64,
+	};
+
+	let mut reads: u64 = 0;
+	let mut writes: u64 = removed;
+	// returns whether `asset_id` holds `location` afterwards
+	let mut insert = |location: &MultiLocation, asset_id: &T::AssetId| {
+		reads += 1;
+		match LocationToAssetId::<T>::get(location) {
+			Some(holder) if holder != *asset_id => {
+				log::warn!(
+					target: "asset-registry",
+					"rebuild_location_index: location {:?} of asset {:?} is already held by asset {:?}, removing it from asset {:?}",
+					location,
+					asset_id,
+					holder,
+					asset_id
+				);
+				false
+			}
+			Some(_) => true,
+			None => {
+				LocationToAssetId::<T>::insert(location, asset_id);
+				writes += 1;
+				true
+			}
+		}
+	};
+
+	let mut lost_locations = Vec::new();
+	for (asset_id, metadata) in Metadata::<T>::iter() {
+		let location = match metadata.location {
+			Some(location) => location,
+			None => continue,
+		};
+		match MultiLocation::try_from(location.clone()) {
+			Ok(latest) => {
+				if !insert(&Pallet::<T>::canonicalize_location(latest), &asset_id) {
+					lost_locations.push(asset_id);
+				}
+			}
+			Err(()) => log::warn!(
+				target: "asset-registry",
+				"rebuild_location_index: can't convert location {:?} of asset {:?}",
+				location,
+				asset_id
+			),
+		}
+	}
+	let mut lost_additional_locations = Vec::new();
+	for (asset_id, location, ()) in AdditionalLocations::<T>::iter() {
+		if !insert(&location, &asset_id) {
+			lost_additional_locations.push((asset_id, location));
+		}
+	}
+
+	// the metadata can't be modified while iterating over it
+	for asset_id in lost_locations {
+		Metadata::<T>::mutate(&asset_id, |maybe_metadata| {
+			if let Some(metadata) = maybe_metadata {
+				metadata.location = None;
+			}
+		});
+		reads += 1;
+		writes += 1;
+	}
+	for (asset_id, location) in lost_additional_locations {
+		AdditionalLocations::<T>::remove(&asset_id, &location);
+		writes += 1;
+	}
+
+	T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Sets `LargestAssetId` to the largest id of the registered assets, for
+/// chains with assets registered before it was introduced. Reads every
+/// asset once, so it can be left in place without harm but should be
+/// removed after it ran.
+pub struct SetLargestAssetId<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for SetLargestAssetId<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut assets: u64 = 0;
+		for asset_id in Metadata::<T>::iter_keys() {
+			assets += 1;
+			Pallet::<T>::note_largest_asset_id(&asset_id);
+		}
+
+		T::DbWeight::get().reads_writes(assets.saturating_mul(2), assets)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		Pallet::<T>::do_try_state()
+	}
+}
+
+/// Rebuilds `LocationToAssetId` with `rebuild_location_index`.
+pub struct RebuildLocationIndex<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for RebuildLocationIndex<T> {
+	fn on_runtime_upgrade() -> Weight {
+		rebuild_location_index::<T>()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		Pallet::<T>::do_try_state()
+	}
+}
+
+/// Re-encodes the location of every asset in the latest XCM version and
+/// rebuilds `LocationToAssetId`, whose keys are concrete `MultiLocation`s,
+/// with `rebuild_location_index`, which is storage version 2.
+///
+/// Locations that can't be converted are kept as-is and left out of
+/// `LocationToAssetId`. Only runs if the on-chain storage version is 1, i.e.
+/// after `BoundNameAndSymbol`, since entries that don't decode as bounded
+/// metadata would be dropped. Does nothing otherwise.
+pub struct MigrateLocationsToLatest<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateLocationsToLatest<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = StorageVersion::get::<Pallet<T>>();
+		if on_chain_version != 1 {
+			log::info!(
+				target: "asset-registry",
+				"MigrateLocationsToLatest: skipping, storage version is {:?}",
+				on_chain_version
+			);
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut assets: u64 = 0;
+		Metadata::<T>::translate::<AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>, _>(
+			|_, mut metadata| {
+				assets += 1;
+				if let Some(location) = metadata.location.take() {
+					metadata.location = Some(match MultiLocation::try_from(location.clone()) {
+						Ok(latest) => latest.into(),
+						Err(()) => location,
+					});
+				}
+				Some(metadata)
+			},
+		);
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get()
+			.reads_writes(assets + 1, assets + 1)
+			.saturating_add(rebuild_location_index::<T>())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		Self::set_temp_storage(LocationToAssetId::<T>::iter_keys().count() as u32, "locations");
+		Self::set_temp_storage(Metadata::<T>::iter_keys().count() as u32, "assets");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		let locations: u32 = Self::get_temp_storage("locations").ok_or("missing location count")?;
+		let assets: u32 = Self::get_temp_storage("assets").ok_or("missing asset count")?;
+		ensure!(
+			StorageVersion::get::<Pallet<T>>() >= 2,
+			"storage version was not updated"
+		);
+		ensure!(
+			LocationToAssetId::<T>::iter_keys().count() as u32 == locations,
+			"LocationToAssetId entries were lost"
+		);
+		ensure!(
+			Metadata::<T>::iter_keys().count() as u32 == assets,
+			"Metadata entries were lost"
+		);
+		Pallet::<T>::do_try_state()
+	}
+}
@@ -5,16 +5,20 @@ use crate as orml_asset_registry;
 use codec::{Decode, Encode};
 use cumulus_primitives_core::{ChannelStatus, GetChannelInfo, ParaId};
 use frame_support::{
-	construct_runtime, match_types, parameter_types,
-	traits::{ConstU128, ConstU32, ConstU64, Everything, Nothing},
+	construct_runtime, ensure, match_types, ord_parameter_types, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, EnsureOneOf, Everything, Nothing},
 	weights::{constants::WEIGHT_PER_SECOND, Weight},
 	PalletId,
 };
-use frame_system::EnsureRoot;
-use orml_asset_registry::{AssetRegistryTrader, FixedRateAssetRegistryTrader};
+use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
+use orml_asset_registry::{
+	AssetMetadata, AssetRegistryTrader, DefaultAssetMetadata, FixedRateAssetRegistryTrader,
+	MetadataConversionRateProvider, SequentialId,
+};
 use orml_traits::{
+	asset_registry::{AssetProcessor, LocationCanonicalizer, MergeAdditional},
 	location::{AbsoluteReserveProvider, RelativeReserveProvider},
-	parameter_type_with_key, FixedConversionRateProvider, MultiCurrency,
+	parameter_type_with_key, MultiCurrency,
 };
 use orml_xcm_support::{IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset};
 use pallet_xcm::XcmPassthrough;
@@ -23,7 +27,7 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{AccountIdConversion, Convert, IdentityLookup},
-	AccountId32,
+	AccountId32, DispatchError,
 };
 use xcm::latest::prelude::*;
 use xcm_builder::{
@@ -100,7 +104,7 @@ impl orml_tokens::Config for Runtime {
 	type OnKilledTokenAccount = ();
 }
 
-#[derive(scale_info::TypeInfo, Encode, Decode, Clone, Eq, PartialEq, Debug)]
+#[derive(scale_info::TypeInfo, Encode, Decode, Clone, Default, Eq, PartialEq, Debug)]
 pub struct CustomMetadata {
 	pub fee_per_second: u128,
 }
@@ -109,12 +113,141 @@ impl orml_asset_registry::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
 	type AssetId = u32;
-	type AuthorityOrigin = EnsureRoot<AccountId>;
+	type RegisterOrigin = EnsureOneOf<EnsureRoot<AccountId>, EnsureSigned<AccountId>>;
+	type UpdateOrigin = EnsureOneOf<EnsureRoot<AccountId>, EnsureSignedBy<Updater, AccountId>>;
+	type AuthorityOrigin = EnsureSignedBy<Registrar, AccountId>;
 	type CustomMetadata = CustomMetadata;
-	type AssetProcessor = orml_asset_registry::SequentialId<Runtime>;
+	type AssetProcessor = TestAssetProcessor;
+	type MergeAdditional = KeepFeeIfUnset;
+	type StringLimit = ConstU32<50>;
+	type EnforceUniqueSymbol = EnforceUniqueSymbol;
+	type AutoRegisterUnknownLocations = AutoRegisterUnknownLocations;
+	type EmitAssetFieldUpdates = EmitAssetFieldUpdates;
+	type EmitPreviousMetadata = EmitPreviousMetadata;
+	type MaxAdditionalLocations = ConstU32<3>;
+	type MaxDecimals = MaxDecimals;
+	type AllowZeroExistentialDeposit = AllowZeroExistentialDeposit;
+	type AllowLocalLocation = AllowLocalLocation;
+	type AllowRegistrationWithDeposit = AllowRegistrationWithDeposit;
+	type LocationCanonicalizer = SelfLocationCanonicalizer;
+	type Currency = Balances;
+	type DepositBase = ConstU128<10>;
+	type DepositPerByte = ConstU128<1>;
 	type WeightInfo = ();
 }
 
+ord_parameter_types! {
+	pub const Registrar: AccountId = AccountId::new([5u8; 32]);
+	pub const Updater: AccountId = AccountId::new([6u8; 32]);
+}
+
+parameter_types! {
+	pub static EnforceUniqueSymbol: bool = false;
+	pub static CanonicalizeSelfLocation: bool = false;
+	pub static NormalizeSymbolsOnUpdate: bool = false;
+	pub static ResolveUnknownLocations: bool = false;
+	pub static AutoRegisterUnknownLocations: bool = false;
+	pub static EmitAssetFieldUpdates: bool = false;
+	pub static EmitPreviousMetadata: bool = false;
+	pub static MergeCustomMetadata: bool = false;
+	pub static MaxDecimals: u32 = 38;
+	pub static AllowZeroExistentialDeposit: bool = true;
+	pub static AllowLocalLocation: bool = false;
+	pub static AllowRegistrationWithDeposit: bool = true;
+}
+
+/// Assigns sequential ids. When `NormalizeSymbolsOnUpdate` is set, updated
+/// symbols are converted to uppercase and non-ASCII symbols are rejected.
+/// When `ResolveUnknownLocations` is set, unknown sibling assets
+/// `(1, X2(Parachain(para_id), GeneralKey(key)))` resolve to asset id
+/// `1000 + para_id`, using `key` as name and symbol.
+pub struct TestAssetProcessor;
+impl AssetProcessor<u32, DefaultAssetMetadata<Runtime>> for TestAssetProcessor {
+	fn pre_register(
+		id: Option<u32>,
+		asset_metadata: DefaultAssetMetadata<Runtime>,
+	) -> Result<(u32, DefaultAssetMetadata<Runtime>), DispatchError> {
+		SequentialId::<Runtime>::pre_register(id, asset_metadata)
+	}
+
+	fn pre_update(
+		_id: &u32,
+		_old_metadata: &DefaultAssetMetadata<Runtime>,
+		mut new_metadata: DefaultAssetMetadata<Runtime>,
+	) -> Result<DefaultAssetMetadata<Runtime>, DispatchError> {
+		if NormalizeSymbolsOnUpdate::get() {
+			ensure!(new_metadata.symbol.is_ascii(), DispatchError::Other("non-ASCII symbol"));
+			new_metadata.symbol = new_metadata
+				.symbol
+				.to_ascii_uppercase()
+				.try_into()
+				.expect("uppercasing doesn't change the length");
+		}
+		Ok(new_metadata)
+	}
+
+	fn resolve_unknown_location(location: &MultiLocation) -> Option<(u32, DefaultAssetMetadata<Runtime>)> {
+		if !ResolveUnknownLocations::get() {
+			return None;
+		}
+		match location {
+			MultiLocation {
+				parents: 1,
+				interior: X2(Parachain(para_id), GeneralKey(key)),
+			} => Some((
+				1000 + para_id,
+				AssetMetadata {
+					decimals: 12,
+					name: key.clone().try_into().ok()?,
+					symbol: key.clone().try_into().ok()?,
+					existential_deposit: 0,
+					location: Some(location.clone().into()),
+					additional: Default::default(),
+				},
+			)),
+			_ => None,
+		}
+	}
+
+	fn note_asset_id(id: &u32) {
+		SequentialId::<Runtime>::note_asset_id(id)
+	}
+
+	fn try_state() -> Result<(), &'static str> {
+		SequentialId::<Runtime>::try_state()
+	}
+}
+
+/// When `MergeCustomMetadata` is set, updates with a `fee_per_second` of zero
+/// keep the stored one.
+pub struct KeepFeeIfUnset;
+impl MergeAdditional<CustomMetadata> for KeepFeeIfUnset {
+	fn merge_additional(old: &CustomMetadata, patch: CustomMetadata) -> CustomMetadata {
+		if MergeCustomMetadata::get() && patch.fee_per_second == 0 {
+			old.clone()
+		} else {
+			patch
+		}
+	}
+}
+
+/// Optionally rewrites locations of this chain as seen from a sibling, e.g.
+/// `(1, X2(Parachain(self), GeneralKey(..)))`, to their local form
+/// `(0, X1(GeneralKey(..)))`.
+pub struct SelfLocationCanonicalizer;
+impl LocationCanonicalizer for SelfLocationCanonicalizer {
+	fn canonicalize(location: MultiLocation) -> MultiLocation {
+		let self_para_id: u32 = ParachainInfo::parachain_id().into();
+		match location.clone() {
+			MultiLocation {
+				parents: 1,
+				interior: X2(Parachain(para_id), junction),
+			} if CanonicalizeSelfLocation::get() && para_id == self_para_id => MultiLocation::new(0, X1(junction)),
+			_ => location,
+		}
+	}
+}
+
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = WEIGHT_PER_SECOND / 4;
 	pub const ReservedDmpWeight: Weight = WEIGHT_PER_SECOND / 4;
@@ -179,10 +312,11 @@ impl TakeRevenue for ToTreasury {
 pub type AssetRegistryWeightTrader =
 	(AssetRegistryTrader<FixedRateAssetRegistryTrader<MyFixedConversionRateProvider>, ToTreasury>,);
 
-pub struct MyFixedConversionRateProvider;
-impl FixedConversionRateProvider for MyFixedConversionRateProvider {
-	fn get_fee_per_second(location: &MultiLocation) -> Option<u128> {
-		let metadata = AssetRegistry::fetch_metadata_by_location(location)?;
+pub type MyFixedConversionRateProvider = MetadataConversionRateProvider<Runtime, FeePerSecondFromMetadata>;
+
+pub struct FeePerSecondFromMetadata;
+impl Convert<DefaultAssetMetadata<Runtime>, Option<u128>> for FeePerSecondFromMetadata {
+	fn convert(metadata: DefaultAssetMetadata<Runtime>) -> Option<u128> {
 		Some(metadata.additional.fee_per_second)
 	}
 }
@@ -335,7 +469,7 @@ construct_runtime!(
 
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
 		XTokens: orml_xtokens::{Pallet, Storage, Call, Event<T>},
-		AssetRegistry: orml_asset_registry::{Pallet, Storage, Call, Event<T>},
+		AssetRegistry: orml_asset_registry::{Pallet, Storage, Call, Event<T>, Config<T>},
 
 		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
 		OrmlXcm: orml_xcm::{Pallet, Call, Event<T>},
@@ -39,11 +39,15 @@ fn print_events<Runtime: frame_system::Config>(name: &'static str) {
 		.for_each(|r| println!("> {:?}", r.event));
 }
 
-fn dummy_metadata() -> AssetMetadata<<para::Runtime as orml_asset_registry::Config>::Balance, CustomMetadata> {
+fn dummy_metadata() -> AssetMetadata<
+	<para::Runtime as orml_asset_registry::Config>::Balance,
+	CustomMetadata,
+	<para::Runtime as orml_asset_registry::Config>::StringLimit,
+> {
 	AssetMetadata {
 		decimals: 12,
-		name: "para A native token".as_bytes().to_vec(),
-		symbol: "paraA".as_bytes().to_vec(),
+		name: "para A native token".as_bytes().to_vec().try_into().unwrap(),
+		symbol: "paraA".as_bytes().to_vec().try_into().unwrap(),
 		existential_deposit: 0,
 		location: Some(MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![0]))).into()),
 		additional: CustomMetadata {
@@ -203,8 +207,8 @@ fn test_sequential_id_normal_behavior() {
 		let metadata1 = dummy_metadata();
 
 		let metadata2 = AssetMetadata {
-			name: "para A native token 2".as_bytes().to_vec(),
-			symbol: "paraA2".as_bytes().to_vec(),
+			name: "para A native token 2".as_bytes().to_vec().try_into().unwrap(),
+			symbol: "paraA2".as_bytes().to_vec().try_into().unwrap(),
 			location: Some(MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1]))).into()),
 			..dummy_metadata()
 		};
@@ -216,6 +220,32 @@ fn test_sequential_id_normal_behavior() {
 	});
 }
 
+#[test]
+fn test_do_register_asset_returns_asset_id() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_eq!(AssetRegistry::do_register_asset(dummy_metadata(), None), Ok(1));
+		assert_eq!(
+			AssetRegistry::do_register_asset(
+				AssetMetadata {
+					location: None,
+					..dummy_metadata()
+				},
+				Some(2)
+			),
+			Ok(2)
+		);
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::RegisteredAsset {
+			asset_id: 2,
+			metadata: AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+		}));
+	});
+}
+
 #[test]
 fn test_sequential_id_with_invalid_id_returns_error() {
 	TestNet::reset();
@@ -229,6 +259,59 @@ fn test_sequential_id_with_invalid_id_returns_error() {
 	});
 }
 
+#[test]
+fn test_sequential_id_overflow_returns_error() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		// the last id is taken, so the id space is full
+		assert_ok!(AssetRegistry::do_register_asset_without_asset_processor(
+			dummy_metadata(),
+			u32::MAX
+		));
+		assert_noop!(
+			AssetRegistry::register_asset(
+				Origin::root(),
+				AssetMetadata {
+					location: None,
+					..dummy_metadata()
+				},
+				None
+			),
+			Error::<para::Runtime>::InvalidAssetId
+		);
+		assert_eq!(AssetRegistry::last_asset_id(), u32::MAX);
+	});
+}
+
+#[test]
+fn test_ranged_sequential_id() {
+	use orml_traits::asset_registry::AssetProcessor;
+
+	frame_support::parameter_types! {
+		pub const Reserved: (u32, u32) = (1, 999);
+	}
+	type Processor = RangedSequentialId<para::Runtime, Reserved>;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_noop!(
+			Processor::pre_register(Some(1), dummy_metadata()),
+			Error::<para::Runtime>::InvalidAssetId
+		);
+		assert_noop!(
+			Processor::pre_register(Some(999), dummy_metadata()),
+			Error::<para::Runtime>::InvalidAssetId
+		);
+
+		// sequential ids start after the reserved range
+		assert_eq!(Processor::pre_register(None, dummy_metadata()).unwrap().0, 1000);
+		assert_eq!(Processor::pre_register(Some(1001), dummy_metadata()).unwrap().0, 1001);
+		assert_eq!(Processor::pre_register(None, dummy_metadata()).unwrap().0, 1002);
+	});
+}
+
 #[test]
 /// tests FixedRateAssetRegistryTrader
 fn test_fixed_rate_asset_trader() {
@@ -379,8 +462,8 @@ fn test_update_metadata_works() {
 
 		let new_metadata = AssetMetadata {
 			decimals: 11,
-			name: "para A native token2".as_bytes().to_vec(),
-			symbol: "paraA2".as_bytes().to_vec(),
+			name: "para A native token2".as_bytes().to_vec().try_into().unwrap(),
+			symbol: "paraA2".as_bytes().to_vec().try_into().unwrap(),
 			existential_deposit: 1,
 			location: Some(MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1]))).into()),
 			additional: CustomMetadata {
@@ -391,8 +474,8 @@ fn test_update_metadata_works() {
 			Origin::root(),
 			1,
 			Some(new_metadata.decimals),
-			Some(new_metadata.name.clone()),
-			Some(new_metadata.symbol.clone()),
+			Some(new_metadata.name.to_vec()),
+			Some(new_metadata.symbol.to_vec()),
 			Some(new_metadata.existential_deposit),
 			Some(new_metadata.location.clone()),
 			Some(new_metadata.additional.clone())
@@ -461,3 +544,2605 @@ fn test_existential_deposits() {
 		);
 	});
 }
+
+#[test]
+fn test_deregister_asset_works() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+
+		let location: MultiLocation = metadata.location.unwrap().try_into().unwrap();
+		assert_eq!(AssetRegistry::metadata(1), None);
+		assert_eq!(AssetRegistry::location_to_asset_id(location), None);
+	});
+}
+
+#[test]
+fn test_deregister_asset_fails_with_unknown_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_noop!(
+			AssetRegistry::deregister_asset(Origin::root(), 1, false),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn test_deregister_asset_frees_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::RemovedLocation {
+			asset_id: 1,
+			location: Box::new(metadata.location.clone().unwrap()),
+		}));
+
+		// the location can be registered again under a new id
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		let location: MultiLocation = metadata.location.unwrap().try_into().unwrap();
+		assert_eq!(AssetRegistry::location_to_asset_id(location), Some(2));
+	});
+}
+
+#[test]
+fn test_set_location_works() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		let new_location: VersionedMultiLocation = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1]))).into();
+		assert_ok!(AssetRegistry::set_location(
+			Origin::root(),
+			1,
+			Box::new(new_location.clone())
+		));
+
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::RemovedLocation {
+			asset_id: 1,
+			location: Box::new(metadata.location.clone().unwrap()),
+		}));
+
+		let old_location: MultiLocation = metadata.location.unwrap().try_into().unwrap();
+		assert_eq!(AssetRegistry::location_to_asset_id(old_location), None);
+		assert_eq!(
+			AssetRegistry::location_to_asset_id(MultiLocation::try_from(new_location.clone()).unwrap()),
+			Some(1)
+		);
+		assert_eq!(AssetRegistry::metadata(1).unwrap().location, Some(new_location));
+	});
+}
+
+#[test]
+fn test_set_location_fails_with_conflicting_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+
+		assert_noop!(
+			AssetRegistry::set_location(Origin::root(), 2, Box::new(metadata.location.clone().unwrap())),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+		assert_noop!(
+			AssetRegistry::set_location(Origin::root(), 3, Box::new(metadata.location.unwrap())),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn test_update_metadata_fails_with_oversized_name() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+
+		assert_noop!(
+			AssetRegistry::update_asset(Origin::root(), 1, None, Some(vec![0; 51]), None, None, None, None),
+			Error::<para::Runtime>::BadMetadata
+		);
+	});
+}
+
+#[test]
+fn test_bound_name_and_symbol_migration_truncates() {
+	use codec::Encode;
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let old_metadata = (
+			12u32,
+			vec![b'a'; 60],
+			b"paraA".to_vec(),
+			0u128,
+			Option::<VersionedMultiLocation>::None,
+			CustomMetadata { fee_per_second: 1 },
+		);
+		frame_support::storage::unhashed::put_raw(
+			&orml_asset_registry::Metadata::<para::Runtime>::hashed_key_for(1),
+			&old_metadata.encode(),
+		);
+
+		assert_eq!(StorageVersion::get::<AssetRegistry>(), 0);
+		migrations::BoundNameAndSymbol::<para::Runtime>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<AssetRegistry>(), 1);
+
+		let metadata = AssetRegistry::metadata(1).unwrap();
+		assert_eq!(metadata.name.to_vec(), vec![b'a'; 50]);
+		assert_eq!(metadata.symbol.to_vec(), b"paraA".to_vec());
+
+		// running it again doesn't touch the already migrated entries
+		migrations::BoundNameAndSymbol::<para::Runtime>::on_runtime_upgrade();
+		assert_eq!(AssetRegistry::metadata(1), Some(metadata));
+	});
+}
+
+#[test]
+fn test_set_largest_asset_id_migration() {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = AssetMetadata {
+			location: None,
+			..dummy_metadata()
+		};
+		assert_ok!(AssetRegistry::do_register_asset_without_asset_processor(
+			metadata.clone(),
+			7
+		));
+		assert_ok!(AssetRegistry::do_register_asset_without_asset_processor(metadata, 3));
+		assert_eq!(AssetRegistry::largest_asset_id(), Some(7));
+
+		// deregistering doesn't lower it, the id may be registered again
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 7, true));
+		assert_eq!(AssetRegistry::largest_asset_id(), Some(7));
+
+		LargestAssetId::<para::Runtime>::kill();
+		assert!(AssetRegistry::do_try_state().is_err());
+
+		migrations::SetLargestAssetId::<para::Runtime>::on_runtime_upgrade();
+		assert_eq!(AssetRegistry::largest_asset_id(), Some(3));
+		assert_ok!(AssetRegistry::do_try_state());
+	});
+}
+
+#[test]
+fn test_assets_and_get_metadata() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata1 = dummy_metadata();
+		let metadata2 = AssetMetadata {
+			location: None,
+			..dummy_metadata()
+		};
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata1.clone(), None));
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata2.clone(), None));
+
+		let mut assets: Vec<_> = AssetRegistry::assets().collect();
+		assets.sort_by_key(|(asset_id, _)| *asset_id);
+		assert_eq!(assets, vec![(1, metadata1.clone()), (2, metadata2)]);
+
+		assert_eq!(
+			AssetRegistry::get_metadata(&[1, 3]),
+			vec![(1, Some(metadata1)), (3, None)]
+		);
+	});
+}
+
+#[test]
+fn test_assets_paginated() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(AssetRegistry::register_asset(
+				Origin::root(),
+				AssetMetadata {
+					location: None,
+					..dummy_metadata()
+				},
+				None
+			));
+		}
+
+		let first_page = AssetRegistry::assets_paginated(None, 2);
+		assert_eq!(first_page.len(), 2);
+
+		let last_key = first_page.last().map(|(asset_id, _)| *asset_id);
+		let second_page = AssetRegistry::assets_paginated(last_key, 2);
+		assert_eq!(second_page.len(), 1);
+
+		let mut asset_ids: Vec<_> = first_page
+			.into_iter()
+			.chain(second_page)
+			.map(|(asset_id, _)| asset_id)
+			.collect();
+		asset_ids.sort();
+		assert_eq!(asset_ids, vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn test_metadata_paged() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(AssetRegistry::register_asset(
+				Origin::root(),
+				AssetMetadata {
+					location: None,
+					..dummy_metadata()
+				},
+				None
+			));
+		}
+
+		let (first_page, next_key) = AssetRegistry::metadata_paged(None, 2);
+		assert_eq!(first_page.len(), 2);
+		assert_eq!(next_key, first_page.last().map(|(asset_id, _)| *asset_id));
+
+		// an empty page keeps the cursor
+		assert_eq!(AssetRegistry::metadata_paged(next_key, 0), (vec![], next_key));
+
+		let (second_page, next_key) = AssetRegistry::metadata_paged(next_key, 2);
+		assert_eq!(second_page.len(), 1);
+		assert_eq!(next_key, None);
+
+		// a page that ends exactly with the last asset has no next page
+		let (all, next_key) = AssetRegistry::metadata_paged(None, 3);
+		assert_eq!(all.len(), 3);
+		assert_eq!(next_key, None);
+
+		let mut asset_ids: Vec<_> = first_page
+			.into_iter()
+			.chain(second_page)
+			.map(|(asset_id, _)| asset_id)
+			.collect();
+		asset_ids.sort();
+		assert_eq!(asset_ids, vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn test_metadata_field_accessors() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		assert_eq!(AssetRegistry::decimals(&1), Some(metadata.decimals));
+		assert_eq!(AssetRegistry::name(&1), Some(metadata.name.to_vec()));
+		assert_eq!(AssetRegistry::symbol(&1), Some(metadata.symbol.to_vec()));
+
+		assert_eq!(AssetRegistry::decimals(&2), None);
+		assert_eq!(AssetRegistry::name(&2), None);
+		assert_eq!(AssetRegistry::symbol(&2), None);
+	});
+}
+
+#[test]
+fn test_fetch_metadata_by_versioned_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		let location = metadata.location.clone().unwrap();
+		assert_eq!(
+			AssetRegistry::fetch_metadata_by_versioned_location(&location),
+			Some(metadata)
+		);
+		assert_eq!(
+			AssetRegistry::fetch_metadata_by_versioned_location(&MultiLocation::new(1, X1(Parachain(3))).into()),
+			None
+		);
+	});
+}
+
+#[test]
+fn test_fetch_asset_id_by_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		let location = metadata.location.unwrap();
+		assert_eq!(
+			AssetRegistry::fetch_asset_id_by_location(&location.clone().try_into().unwrap()),
+			Some(1)
+		);
+		assert_eq!(AssetRegistry::fetch_asset_id_by_versioned_location(&location), Some(1));
+		assert_eq!(
+			AssetRegistry::fetch_asset_id_by_location(&MultiLocation::new(1, X1(Parachain(3)))),
+			None
+		);
+	});
+}
+
+#[test]
+fn test_call_indices_are_stable() {
+	use codec::Encode;
+
+	let index = |call: crate::Call<para::Runtime>| call.encode()[0];
+
+	assert_eq!(
+		index(crate::Call::register_asset {
+			metadata: dummy_metadata(),
+			asset_id: None
+		}),
+		0
+	);
+	assert_eq!(
+		index(crate::Call::update_asset {
+			asset_id: 1,
+			decimals: None,
+			name: None,
+			symbol: None,
+			existential_deposit: None,
+			location: None,
+			additional: None
+		}),
+		1
+	);
+	assert_eq!(
+		index(crate::Call::deregister_asset {
+			asset_id: 1,
+			reusable: false
+		}),
+		2
+	);
+	assert_eq!(index(crate::Call::update_assets { updates: vec![] }), 12);
+}
+
+#[test]
+fn test_inspect_works() {
+	use orml_traits::asset_registry::Inspect;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		let location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		assert_eq!(<AssetRegistry as Inspect>::asset_id(&location), Some(1));
+		assert_eq!(<AssetRegistry as Inspect>::metadata(&1), Some(metadata.clone()));
+		assert_eq!(
+			<AssetRegistry as Inspect>::metadata_by_location(&location),
+			Some(metadata.clone())
+		);
+		assert_eq!(<AssetRegistry as Inspect>::location(&1), Ok(Some(location)));
+		assert_eq!(<AssetRegistry as Inspect>::ensure_asset_exists(&1), Ok(metadata));
+		assert_eq!(
+			<AssetRegistry as Inspect>::ensure_asset_exists(&2),
+			Err(Error::<para::Runtime>::AssetNotFound.into())
+		);
+	});
+}
+
+#[test]
+fn test_try_get_metadata() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+
+		assert_eq!(AssetRegistry::try_get_metadata(&1), Ok(dummy_metadata()));
+		assert_eq!(
+			AssetRegistry::try_get_metadata(&2),
+			Err(Error::<para::Runtime>::AssetNotFound.into())
+		);
+	});
+}
+
+#[test]
+fn test_mutate_works() {
+	use orml_traits::asset_registry::Mutate;
+
+	TestNet::reset();
+	para::MergeCustomMetadata::set(true);
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_eq!(<AssetRegistry as Mutate>::register_asset(metadata.clone(), None), Ok(1));
+		assert_eq!(AssetRegistry::metadata(1), Some(metadata.clone()));
+
+		assert_ok!(<AssetRegistry as Mutate>::update_asset(
+			1,
+			Some(18),
+			None,
+			None,
+			None,
+			Some(None),
+			None
+		));
+		assert_eq!(
+			AssetRegistry::metadata(1),
+			Some(AssetMetadata {
+				decimals: 18,
+				location: None,
+				..metadata.clone()
+			})
+		);
+
+		assert_noop!(
+			<AssetRegistry as Mutate>::update_asset(2, Some(18), None, None, None, None, None),
+			Error::<para::Runtime>::AssetNotFound
+		);
+
+		assert_ok!(<AssetRegistry as Mutate>::mutate_additional(1, |additional| {
+			additional.fee_per_second *= 2
+		}));
+		let updated = AssetRegistry::metadata(1).unwrap();
+		assert_eq!(
+			updated.additional.fee_per_second,
+			2 * metadata.additional.fee_per_second
+		);
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::UpdatedAsset {
+			asset_id: 1,
+			metadata: updated,
+		}));
+		// the mutated value is written as is, without `MergeAdditional`
+		assert_ok!(<AssetRegistry as Mutate>::mutate_additional(1, |additional| {
+			additional.fee_per_second = 0
+		}));
+		assert_eq!(AssetRegistry::metadata(1).unwrap().additional.fee_per_second, 0);
+
+		assert_noop!(
+			<AssetRegistry as Mutate>::mutate_additional(2, |_| {}),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+
+	para::MergeCustomMetadata::set(false);
+}
+
+#[test]
+fn test_genesis_registers_assets() {
+	use codec::Encode;
+	use frame_support::traits::GenesisBuild;
+
+	let metadata = dummy_metadata();
+
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<para::Runtime>()
+		.unwrap();
+	orml_asset_registry::GenesisConfig::<para::Runtime> {
+		assets: vec![(None, metadata.encode())],
+		last_asset_id: 10,
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		let location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		assert_eq!(AssetRegistry::metadata(11), Some(metadata));
+		assert_eq!(AssetRegistry::location_to_asset_id(location), Some(11));
+		assert_eq!(AssetRegistry::last_asset_id(), 11);
+	});
+}
+
+#[test]
+fn test_additional_locations_work() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		let alias: VersionedMultiLocation = MultiLocation::new(0, X1(GeneralKey(vec![0]))).into();
+		let alias_location: MultiLocation = alias.clone().try_into().unwrap();
+		assert_ok!(AssetRegistry::add_location(Origin::root(), 1, Box::new(alias.clone())));
+
+		assert_eq!(AssetRegistry::location_to_asset_id(&alias_location), Some(1));
+		assert_eq!(
+			AssetRegistry::fetch_metadata_by_location(&alias_location),
+			Some(metadata.clone())
+		);
+
+		// the canonical location can't be removed this way
+		assert_noop!(
+			AssetRegistry::remove_location(Origin::root(), 1, Box::new(metadata.location.clone().unwrap())),
+			Error::<para::Runtime>::LocationNotFound
+		);
+
+		assert_ok!(AssetRegistry::remove_location(
+			Origin::root(),
+			1,
+			Box::new(alias.clone())
+		));
+		assert_eq!(AssetRegistry::location_to_asset_id(&alias_location), None);
+		assert_eq!(AssetRegistry::metadata(1).unwrap().location, metadata.location);
+	});
+}
+
+#[test]
+fn test_additional_locations_conflicts_and_cleanup() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		assert_noop!(
+			AssetRegistry::add_location(Origin::root(), 1, Box::new(metadata.location.clone().unwrap())),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+		assert_noop!(
+			AssetRegistry::add_location(Origin::root(), 2, Box::new(metadata.location.clone().unwrap())),
+			Error::<para::Runtime>::AssetNotFound
+		);
+
+		let alias: VersionedMultiLocation = MultiLocation::new(0, X1(GeneralKey(vec![0]))).into();
+		assert_ok!(AssetRegistry::add_location(Origin::root(), 1, Box::new(alias.clone())));
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+
+		let alias_location: MultiLocation = alias.try_into().unwrap();
+		assert_eq!(AssetRegistry::location_to_asset_id(&alias_location), None);
+		assert_eq!(AssetRegistry::additional_locations(1, alias_location), None);
+	});
+}
+
+#[test]
+fn test_additional_locations_are_bounded() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+
+		let additional = |key: u8| -> VersionedMultiLocation {
+			MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![key]))).into()
+		};
+		for key in 1..=3 {
+			assert_ok!(AssetRegistry::add_location(
+				Origin::root(),
+				1,
+				Box::new(additional(key))
+			));
+		}
+		assert_noop!(
+			AssetRegistry::add_location(Origin::root(), 1, Box::new(additional(4))),
+			Error::<para::Runtime>::TooManyLocations
+		);
+
+		// removing one makes room again
+		assert_ok!(AssetRegistry::remove_location(
+			Origin::root(),
+			1,
+			Box::new(additional(1))
+		));
+		assert_ok!(AssetRegistry::add_location(Origin::root(), 1, Box::new(additional(4))));
+	});
+}
+
+#[test]
+fn test_additional_locations_are_unique_across_assets() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				symbol: "paraB".as_bytes().to_vec().try_into().unwrap(),
+				location: None,
+				..metadata.clone()
+			},
+			None
+		));
+
+		let alias: VersionedMultiLocation = MultiLocation::new(0, X1(GeneralKey(vec![0]))).into();
+		assert_ok!(AssetRegistry::add_location(Origin::root(), 1, Box::new(alias.clone())));
+
+		// neither as an additional nor as the primary location of another asset
+		assert_noop!(
+			AssetRegistry::add_location(Origin::root(), 2, Box::new(alias.clone())),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+		assert_noop!(
+			AssetRegistry::set_location(Origin::root(), 2, Box::new(alias.clone())),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+		assert_noop!(
+			AssetRegistry::add_location(Origin::root(), 2, Box::new(metadata.location.clone().unwrap())),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+	});
+}
+
+#[test]
+fn test_deregistered_asset_id_reuse() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = AssetMetadata {
+			location: None,
+			..dummy_metadata()
+		};
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		// retired ids can't be registered again
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+		assert_noop!(
+			AssetRegistry::do_register_asset_without_asset_processor(metadata.clone(), 1),
+			Error::<para::Runtime>::ConflictingAssetId
+		);
+
+		// reusable ids can
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 2, true));
+		assert_ok!(AssetRegistry::do_register_asset_without_asset_processor(
+			metadata.clone(),
+			2
+		));
+		assert_eq!(AssetRegistry::metadata(2), Some(metadata));
+		assert_eq!(AssetRegistry::last_asset_id(), 2);
+	});
+}
+
+#[test]
+fn test_unique_symbol_enforced() {
+	TestNet::reset();
+	para::EnforceUniqueSymbol::set(true);
+
+	ParaA::execute_with(|| {
+		let metadata = AssetMetadata {
+			location: None,
+			..dummy_metadata()
+		};
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert_noop!(
+			AssetRegistry::register_asset(Origin::root(), metadata.clone(), None),
+			Error::<para::Runtime>::ConflictingSymbol
+		);
+
+		// renaming frees up the old symbol
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			None,
+			None,
+			Some(b"paraB".to_vec()),
+			None,
+			None,
+			None
+		));
+		assert_eq!(AssetRegistry::symbol_to_asset_id(metadata.symbol.clone()), None);
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert_eq!(AssetRegistry::symbol_to_asset_id(metadata.symbol.clone()), Some(2));
+		assert_eq!(AssetRegistry::asset_id_by_symbol(b"paraA"), Some(2));
+		assert_eq!(AssetRegistry::asset_id_by_symbol(&[0; 51]), None);
+
+		assert_noop!(
+			AssetRegistry::update_asset(
+				Origin::root(),
+				1,
+				None,
+				None,
+				Some(metadata.symbol.to_vec()),
+				None,
+				None,
+				None
+			),
+			Error::<para::Runtime>::ConflictingSymbol
+		);
+
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 2, false));
+		assert_eq!(AssetRegistry::symbol_to_asset_id(metadata.symbol), None);
+	});
+}
+
+#[test]
+fn test_duplicate_symbols_allowed_by_default() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = AssetMetadata {
+			location: None,
+			..dummy_metadata()
+		};
+		assert_ok!(AssetRegistry::do_register_asset_without_asset_processor(
+			metadata.clone(),
+			2
+		));
+		assert_ok!(AssetRegistry::do_register_asset_without_asset_processor(
+			metadata.clone(),
+			1
+		));
+		assert_eq!(AssetRegistry::symbol_to_asset_id(metadata.symbol.clone()), None);
+
+		// the lowest id wins
+		assert_eq!(AssetRegistry::asset_id_by_symbol(&metadata.symbol), Some(1));
+		assert_eq!(AssetRegistry::asset_id_by_symbol(b"paraB"), None);
+	});
+}
+
+#[test]
+fn test_fungibles_with_metadata() {
+	use frame_support::traits::tokens::{
+		fungibles::{self, InspectMetadata},
+		DepositConsequence, WithdrawConsequence,
+	};
+
+	struct NoBalances;
+	impl fungibles::Inspect<AccountId32> for NoBalances {
+		type AssetId = u32;
+		type Balance = u128;
+
+		fn total_issuance(_: u32) -> u128 {
+			0
+		}
+		fn minimum_balance(_: u32) -> u128 {
+			0
+		}
+		fn balance(_: u32, _: &AccountId32) -> u128 {
+			0
+		}
+		fn reducible_balance(_: u32, _: &AccountId32, _: bool) -> u128 {
+			0
+		}
+		fn can_deposit(_: u32, _: &AccountId32, _: u128, _: bool) -> DepositConsequence {
+			DepositConsequence::UnknownAsset
+		}
+		fn can_withdraw(_: u32, _: &AccountId32, _: u128) -> WithdrawConsequence<u128> {
+			WithdrawConsequence::UnknownAsset
+		}
+	}
+
+	type Fungibles = FungiblesWithMetadata<para::Runtime, NoBalances>;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::MaxDecimals::set(u32::MAX);
+
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				decimals: 300,
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+
+		assert_eq!(
+			<Fungibles as InspectMetadata<AccountId32>>::name(&1),
+			metadata.name.to_vec()
+		);
+		assert_eq!(
+			<Fungibles as InspectMetadata<AccountId32>>::symbol(&1),
+			metadata.symbol.to_vec()
+		);
+		assert_eq!(<Fungibles as InspectMetadata<AccountId32>>::decimals(&1), 12);
+		assert_eq!(<Fungibles as InspectMetadata<AccountId32>>::decimals(&2), u8::MAX);
+		assert_eq!(<Fungibles as InspectMetadata<AccountId32>>::decimals(&3), 0);
+
+		para::MaxDecimals::set(38);
+	});
+}
+
+#[test]
+fn test_existential_deposits_from_registry() {
+	use frame_support::traits::ConstU128;
+	use orml_traits::GetByKey;
+
+	type ExistentialDeposits = ExistentialDepositsFromRegistry<para::Runtime, ConstU128<7>>;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = AssetMetadata {
+			existential_deposit: 100,
+			..dummy_metadata()
+		};
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+
+		assert_eq!(ExistentialDeposits::get(&1), 100);
+		assert_eq!(ExistentialDeposits::get(&2), 7);
+		assert_eq!(<ExistentialDepositsFromRegistry<para::Runtime>>::get(&2), 0);
+	});
+}
+
+#[test]
+fn test_removed_location_event() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			None,
+			None,
+			None,
+			None,
+			Some(None),
+			None
+		));
+
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::RemovedLocation {
+			asset_id: 1,
+			location: Box::new(metadata.location.unwrap()),
+		}));
+	});
+}
+
+#[test]
+fn test_try_state_detects_inconsistencies() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert_ok!(AssetRegistry::add_location(
+			Origin::root(),
+			1,
+			Box::new(MultiLocation::new(0, X1(GeneralKey(vec![0]))).into())
+		));
+		assert_ok!(AssetRegistry::do_try_state());
+
+		// a location pointing to an unknown asset
+		let location: MultiLocation = metadata.location.unwrap().try_into().unwrap();
+		LocationToAssetId::<para::Runtime>::insert(&location, 2);
+		assert!(AssetRegistry::do_try_state().is_err());
+		LocationToAssetId::<para::Runtime>::insert(&location, 1);
+		assert_ok!(AssetRegistry::do_try_state());
+
+		// an additional location missing from LocationToAssetId
+		let orphan = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1])));
+		AdditionalLocations::<para::Runtime>::insert(1, &orphan, ());
+		assert!(AssetRegistry::do_try_state().is_err());
+		AdditionalLocations::<para::Runtime>::remove(1, &orphan);
+		assert_ok!(AssetRegistry::do_try_state());
+
+		// an asset id beyond the sequential counter
+		LastAssetId::<para::Runtime>::put(0);
+		assert!(AssetRegistry::do_try_state().is_err());
+	});
+}
+
+#[test]
+fn test_force_set_location_takes_over_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: Some(MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1]))).into()),
+				..dummy_metadata()
+			},
+			None
+		));
+
+		let location = metadata.location.clone().unwrap();
+		assert_noop!(
+			AssetRegistry::set_location(Origin::root(), 2, Box::new(location.clone())),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+		assert_ok!(AssetRegistry::force_set_location(
+			Origin::root(),
+			2,
+			Box::new(location.clone())
+		));
+
+		let latest_location: MultiLocation = location.clone().try_into().unwrap();
+		assert_eq!(AssetRegistry::location_to_asset_id(latest_location), Some(2));
+		assert_eq!(AssetRegistry::metadata(1).unwrap().location, None);
+		assert_eq!(AssetRegistry::metadata(2).unwrap().location, Some(location));
+		assert_ok!(AssetRegistry::do_try_state());
+	});
+}
+
+#[test]
+fn test_force_set_location_relabels_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let location = metadata.location.clone().unwrap();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+
+		assert_noop!(
+			AssetRegistry::force_set_location(Origin::root(), 3, Box::new(location.clone())),
+			Error::<para::Runtime>::AssetNotFound
+		);
+
+		assert_ok!(AssetRegistry::force_set_location(
+			Origin::root(),
+			2,
+			Box::new(location.clone())
+		));
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::RemovedLocation {
+			asset_id: 1,
+			location: Box::new(location.clone()),
+		}));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::SetLocation {
+			asset_id: 2,
+			location: Box::new(location.clone()),
+		}));
+
+		let latest_location: MultiLocation = location.clone().try_into().unwrap();
+		assert_eq!(AssetRegistry::location_to_asset_id(latest_location), Some(2));
+		assert_eq!(AssetRegistry::metadata(1).unwrap().location, None);
+		assert_eq!(AssetRegistry::metadata(2).unwrap().location, Some(location));
+	});
+}
+
+#[test]
+fn test_location_changes_update_metadata_bookkeeping() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let registrar = para::Registrar::get();
+		para::Balances::make_free_balance_be(&registrar, 1_000);
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::signed(registrar.clone()),
+			dummy_metadata(),
+			None
+		));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+
+		// a longer location needs a larger deposit
+		para::System::set_block_number(5);
+		let location: VersionedMultiLocation = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1; 32]))).into();
+		assert_ok!(AssetRegistry::set_location(
+			Origin::root(),
+			1,
+			Box::new(location.clone())
+		));
+		let metadata = AssetRegistry::metadata(1).unwrap();
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::UpdatedAsset {
+			asset_id: 1,
+			metadata: metadata.clone(),
+		}));
+		let deposit = AssetRegistry::deposit_for(&metadata);
+		assert_eq!(para::Balances::reserved_balance(&registrar), deposit);
+		assert_eq!(AssetRegistry::updated_at(&1), Some(5));
+
+		// taking the location away clears it from the metadata of its holder
+		para::System::set_block_number(7);
+		assert_ok!(AssetRegistry::force_set_location(Origin::root(), 2, Box::new(location)));
+		let metadata = AssetRegistry::metadata(1).unwrap();
+		assert_eq!(metadata.location, None);
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::UpdatedAsset {
+			asset_id: 1,
+			metadata: metadata.clone(),
+		}));
+		let deposit = AssetRegistry::deposit_for(&metadata);
+		assert_eq!(para::Balances::reserved_balance(&registrar), deposit);
+		assert_eq!(AssetRegistry::deposits(1), Some((registrar, deposit)));
+		assert_eq!(AssetRegistry::updated_at(&1), Some(7));
+		assert_eq!(AssetRegistry::updated_at(&2), Some(7));
+		assert_ok!(AssetRegistry::do_try_state());
+	});
+}
+
+#[test]
+fn test_freeze_and_thaw_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		assert!(!AssetRegistry::is_frozen(&1));
+
+		assert_ok!(AssetRegistry::freeze_asset(Origin::root(), 1));
+		assert!(AssetRegistry::is_frozen(&1));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::FrozenAsset { asset_id: 1 }));
+
+		// updates leave the asset frozen
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			None,
+			None,
+			Some(b"FRZ".to_vec()),
+			None,
+			None,
+			None
+		));
+		assert!(AssetRegistry::is_frozen(&1));
+
+		assert_ok!(AssetRegistry::thaw_asset(Origin::root(), 1));
+		assert!(!AssetRegistry::is_frozen(&1));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::ThawedAsset { asset_id: 1 }));
+
+		assert_noop!(
+			AssetRegistry::freeze_asset(Origin::root(), 2),
+			Error::<para::Runtime>::AssetNotFound
+		);
+		assert!(!AssetRegistry::is_frozen(&2));
+
+		// deregistering clears the flag, so a reused id starts out thawed
+		assert_ok!(AssetRegistry::freeze_asset(Origin::root(), 1));
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, true));
+		assert!(!AssetRegistry::is_frozen(&1));
+	});
+}
+
+#[test]
+fn test_migrate_locations_to_latest() {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		let old_location = xcm::v0::MultiLocation::try_from(location.clone()).unwrap();
+
+		// store the location in an older version and lose the reverse mapping
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+		Metadata::<para::Runtime>::mutate(1, |metadata| {
+			metadata.as_mut().unwrap().location = Some(VersionedMultiLocation::V0(old_location))
+		});
+		LocationToAssetId::<para::Runtime>::remove(&location);
+
+		// only runs on top of `BoundNameAndSymbol`
+		migrations::MigrateLocationsToLatest::<para::Runtime>::on_runtime_upgrade();
+		assert_eq!(AssetRegistry::location_to_asset_id(location.clone()), None);
+
+		StorageVersion::new(1).put::<AssetRegistry>();
+		migrations::MigrateLocationsToLatest::<para::Runtime>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<AssetRegistry>(), 2);
+
+		assert_eq!(
+			AssetRegistry::metadata(1).unwrap().location,
+			Some(VersionedMultiLocation::V1(location.clone()))
+		);
+		assert_eq!(AssetRegistry::location_to_asset_id(location), Some(1));
+		assert_ok!(AssetRegistry::do_try_state());
+	});
+}
+
+#[test]
+fn test_rebuild_location_index() {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		let additional = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1])));
+		let stale = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![2])));
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+		assert_ok!(AssetRegistry::add_location(
+			Origin::root(),
+			1,
+			Box::new(additional.clone().into())
+		));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+
+		// let the index drift from the metadata, and let asset 2 claim the
+		// location of asset 1
+		LocationToAssetId::<para::Runtime>::insert(&location, 2);
+		LocationToAssetId::<para::Runtime>::remove(&additional);
+		LocationToAssetId::<para::Runtime>::insert(&stale, 1);
+		Metadata::<para::Runtime>::mutate(2, |metadata| {
+			metadata.as_mut().unwrap().location = Some(location.clone().into())
+		});
+
+		migrations::RebuildLocationIndex::<para::Runtime>::on_runtime_upgrade();
+
+		// the conflicting location stays with the asset visited first and is
+		// removed from the other one
+		let first = Metadata::<para::Runtime>::iter_keys().next().unwrap();
+		let second = if first == 1 { 2 } else { 1 };
+		assert_eq!(AssetRegistry::location_to_asset_id(location.clone()), Some(first));
+		assert_eq!(AssetRegistry::metadata(first).unwrap().location, Some(location.into()));
+		assert_eq!(AssetRegistry::metadata(second).unwrap().location, None);
+		assert_eq!(AssetRegistry::location_to_asset_id(additional), Some(1));
+		assert_eq!(AssetRegistry::location_to_asset_id(stale), None);
+		assert_eq!(LocationToAssetId::<para::Runtime>::iter().count(), 2);
+		assert_ok!(AssetRegistry::do_try_state());
+	});
+}
+
+#[test]
+fn test_canonically_equal_locations_conflict() {
+	TestNet::reset();
+	para::CanonicalizeSelfLocation::set(true);
+
+	ParaA::execute_with(|| {
+		let sibling_view = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![0])));
+		let local_view = MultiLocation::new(0, X1(GeneralKey(vec![0])));
+
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: Some(sibling_view.clone().into()),
+				..dummy_metadata()
+			},
+			None
+		));
+		assert_noop!(
+			AssetRegistry::register_asset(
+				Origin::root(),
+				AssetMetadata {
+					location: Some(local_view.clone().into()),
+					..dummy_metadata()
+				},
+				None
+			),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+
+		// both forms resolve to the same asset
+		assert_eq!(AssetRegistry::location_to_asset_id(&local_view), Some(1));
+		assert!(AssetRegistry::fetch_metadata_by_location(&sibling_view).is_some());
+		assert!(AssetRegistry::fetch_metadata_by_location(&local_view).is_some());
+		assert_ok!(AssetRegistry::do_try_state());
+	});
+}
+
+#[test]
+fn test_explicit_asset_id_advances_last_asset_id() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = AssetMetadata {
+			location: None,
+			..dummy_metadata()
+		};
+		assert_eq!(
+			AssetRegistry::do_register_asset_without_asset_processor(metadata.clone(), 5),
+			Ok(5)
+		);
+		assert_eq!(AssetRegistry::last_asset_id(), 5);
+
+		// the next sequential id doesn't collide
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+		assert!(AssetRegistry::metadata(6).is_some());
+
+		assert_ok!(AssetRegistry::set_last_asset_id(Origin::root(), 10));
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+		assert!(AssetRegistry::metadata(11).is_some());
+
+		// it can't be set below a registered id
+		assert_noop!(
+			AssetRegistry::set_last_asset_id(Origin::root(), 10),
+			Error::<para::Runtime>::InvalidAssetId
+		);
+		assert_ok!(AssetRegistry::set_last_asset_id(Origin::root(), 11));
+		assert_eq!(AssetRegistry::last_asset_id(), 11);
+	});
+}
+
+#[test]
+fn test_register_asset_batch() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata1 = dummy_metadata();
+		let metadata2 = AssetMetadata {
+			location: None,
+			..dummy_metadata()
+		};
+		assert_ok!(AssetRegistry::register_asset_batch(
+			Origin::root(),
+			vec![(metadata1.clone(), None), (metadata2.clone(), None)]
+		));
+		assert_eq!(AssetRegistry::metadata(1), Some(metadata1.clone()));
+		assert_eq!(AssetRegistry::metadata(2), Some(metadata2.clone()));
+
+		// a failing registration rolls back the whole batch
+		assert_noop!(
+			AssetRegistry::register_asset_batch(Origin::root(), vec![(metadata2, None), (metadata1, None)]),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+	});
+}
+
+#[test]
+fn test_asset_ids_under() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let locations = vec![
+			MultiLocation::new(1, X2(Parachain(2), GeneralKey(vec![0]))),
+			MultiLocation::new(1, X2(Parachain(2), GeneralKey(vec![1]))),
+			MultiLocation::new(1, X2(Parachain(3), GeneralKey(vec![0]))),
+			MultiLocation::new(1, Here),
+		];
+		for location in locations {
+			assert_ok!(AssetRegistry::register_asset(
+				Origin::root(),
+				AssetMetadata {
+					location: Some(location.into()),
+					..dummy_metadata()
+				},
+				None
+			));
+		}
+
+		let mut sibling_2 = AssetRegistry::asset_ids_under(&MultiLocation::new(1, X1(Parachain(2))));
+		sibling_2.sort();
+		assert_eq!(sibling_2, vec![1, 2]);
+
+		assert_eq!(
+			AssetRegistry::asset_ids_under(&MultiLocation::new(1, X2(Parachain(3), GeneralKey(vec![0])))),
+			vec![3]
+		);
+		assert_eq!(
+			AssetRegistry::asset_ids_under(&MultiLocation::new(1, X1(Parachain(4)))),
+			Vec::<u32>::new()
+		);
+		assert_eq!(
+			AssetRegistry::asset_ids_under(&MultiLocation::new(0, Here)),
+			Vec::<u32>::new()
+		);
+
+		let mut parent = AssetRegistry::asset_ids_under(&MultiLocation::parent());
+		parent.sort();
+		assert_eq!(parent, vec![1, 2, 3, 4]);
+	});
+}
+
+#[test]
+fn test_registered_locations() {
+	use frame_support::traits::Contains;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		assert!(!RegisteredLocations::<para::Runtime>::contains(&location));
+
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+		assert!(RegisteredLocations::<para::Runtime>::contains(&location));
+
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+		assert!(!RegisteredLocations::<para::Runtime>::contains(&location));
+	});
+}
+
+#[test]
+fn test_location_asset_id_conversions() {
+	use sp_runtime::traits::Convert;
+	use xcm_executor::traits::Convert as XcmConvert;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+
+		assert_eq!(
+			<AssetIdToLocation<para::Runtime> as Convert<_, _>>::convert(1),
+			Some(location.clone())
+		);
+		assert_eq!(<AssetIdToLocation<para::Runtime> as Convert<_, _>>::convert(2), None);
+		assert_eq!(
+			<AssetIdToLocation<para::Runtime> as XcmConvert<_, MultiLocation>>::convert(1),
+			Ok(location.clone())
+		);
+		assert_eq!(
+			<AssetIdToLocation<para::Runtime> as XcmConvert<_, MultiLocation>>::convert(2),
+			Err(2)
+		);
+		assert_eq!(
+			<AssetIdToLocation<para::Runtime> as XcmConvert<u32, _>>::reverse(location.clone()),
+			Ok(1)
+		);
+		assert_eq!(
+			<LocationToAssetIdConvert<para::Runtime> as Convert<_, _>>::convert(location.clone()),
+			Some(1)
+		);
+
+		assert_eq!(
+			<LocationToAssetIdConvert<para::Runtime> as XcmConvert<_, _>>::convert(location.clone()),
+			Ok(1)
+		);
+		assert_eq!(
+			<LocationToAssetIdConvert<para::Runtime> as XcmConvert<_, u32>>::reverse(1),
+			Ok(location)
+		);
+		let unknown = MultiLocation::new(1, X1(Parachain(3)));
+		assert_eq!(
+			<LocationToAssetIdConvert<para::Runtime> as XcmConvert<_, u32>>::convert(unknown.clone()),
+			Err(unknown)
+		);
+		assert_eq!(
+			<LocationToAssetIdConvert<para::Runtime> as XcmConvert<MultiLocation, _>>::reverse(2),
+			Err(2)
+		);
+	});
+}
+
+#[test]
+fn test_asset_processor_update_hooks() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		para::NormalizeSymbolsOnUpdate::set(true);
+
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			None,
+			None,
+			Some(b"xyz".to_vec()),
+			None,
+			None,
+			None
+		));
+		assert_eq!(AssetRegistry::metadata(1).unwrap().symbol.to_vec(), b"XYZ".to_vec());
+
+		assert_noop!(
+			AssetRegistry::update_asset(
+				Origin::root(),
+				1,
+				None,
+				None,
+				Some("xÿz".as_bytes().to_vec()),
+				None,
+				None,
+				None
+			),
+			DispatchError::Other("non-ASCII symbol")
+		);
+
+		para::NormalizeSymbolsOnUpdate::set(false);
+	});
+}
+
+#[test]
+fn test_set_xcm_enabled() {
+	use frame_support::traits::Contains;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+		assert!(AssetRegistry::is_xcm_enabled(&1));
+		assert!(XcmEnabledLocations::<para::Runtime>::contains(&location));
+
+		assert_ok!(AssetRegistry::set_xcm_enabled(Origin::root(), 1, false));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::SetXcmEnabled {
+			asset_id: 1,
+			enabled: false,
+		}));
+		assert!(!AssetRegistry::is_xcm_enabled(&1));
+		assert!(!XcmEnabledLocations::<para::Runtime>::contains(&location));
+		// the asset is still registered
+		assert!(RegisteredLocations::<para::Runtime>::contains(&location));
+
+		assert_ok!(AssetRegistry::set_xcm_enabled(Origin::root(), 1, true));
+		assert!(XcmEnabledLocations::<para::Runtime>::contains(&location));
+
+		assert_noop!(
+			AssetRegistry::set_xcm_enabled(Origin::root(), 2, false),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn test_update_asset_clears_fields() {
+	TestNet::reset();
+	para::EnforceUniqueSymbol::set(true);
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				symbol: b"paraB".to_vec().try_into().unwrap(),
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+
+		for asset_id in [1, 2] {
+			assert_ok!(AssetRegistry::update_asset(
+				Origin::root(),
+				asset_id,
+				None,
+				Some(vec![]),
+				Some(vec![]),
+				None,
+				Some(None),
+				None
+			));
+		}
+
+		let metadata = AssetRegistry::metadata(1).unwrap();
+		assert!(metadata.name.is_empty());
+		assert!(metadata.symbol.is_empty());
+		assert_eq!(metadata.location, None);
+		// empty symbols don't conflict
+		assert!(AssetRegistry::metadata(2).unwrap().symbol.is_empty());
+		assert_eq!(AssetRegistry::asset_id_by_symbol(b""), None);
+	});
+}
+
+#[test]
+fn test_update_asset_location_none_vs_some_none() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let location = metadata.location.clone().unwrap();
+		let latest_location: MultiLocation = location.clone().try_into().unwrap();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+
+		// `None` leaves the location unchanged
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			Some(18),
+			None,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(AssetRegistry::metadata(1).unwrap().location, Some(location.clone()));
+		assert_eq!(AssetRegistry::location_to_asset_id(latest_location.clone()), Some(1));
+
+		// `Some(None)` clears it, including the reverse mapping
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			None,
+			None,
+			None,
+			None,
+			Some(None),
+			None
+		));
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::RemovedLocation {
+			asset_id: 1,
+			location: Box::new(location.clone()),
+		}));
+		assert_eq!(AssetRegistry::metadata(1).unwrap().location, None);
+		assert_eq!(AssetRegistry::location_to_asset_id(latest_location.clone()), None);
+
+		// so that the location can be registered for another asset
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		assert_eq!(AssetRegistry::location_to_asset_id(latest_location), Some(2));
+		assert_ok!(AssetRegistry::do_try_state());
+	});
+}
+
+#[test]
+fn test_update_assets() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+
+		let update = |asset_id, existential_deposit| AssetUpdate::<para::Runtime> {
+			asset_id,
+			decimals: None,
+			name: None,
+			symbol: None,
+			existential_deposit: Some(existential_deposit),
+			location: None,
+			additional: None,
+		};
+
+		assert_ok!(AssetRegistry::update_assets(
+			Origin::root(),
+			vec![update(1, 10), update(2, 20)]
+		));
+		assert_eq!(AssetRegistry::metadata(1).unwrap().existential_deposit, 10);
+		assert_eq!(AssetRegistry::metadata(2).unwrap().existential_deposit, 20);
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::UpdatedAsset {
+			asset_id: 1,
+			metadata: AssetMetadata {
+				existential_deposit: 10,
+				..dummy_metadata()
+			},
+		}));
+
+		// a failing update rolls back the whole batch
+		assert_noop!(
+			AssetRegistry::update_assets(Origin::root(), vec![update(1, 30), update(3, 30)]),
+			Error::<para::Runtime>::AssetNotFound
+		);
+		assert_eq!(
+			AssetRegistry::update_assets_weight(&[update(1, 10), update(2, 20)]),
+			2 * AssetRegistry::update_asset_weight(false, false, false, true, false, false)
+		);
+	});
+}
+
+#[test]
+fn test_registration_deposit() {
+	use frame_support::traits::{Currency, ReservableCurrency};
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let registrar = para::Registrar::get();
+		para::Balances::make_free_balance_be(&registrar, 1_000);
+
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::signed(registrar.clone()),
+			metadata.clone(),
+			None
+		));
+		let deposit = AssetRegistry::deposit_for(&metadata);
+		assert_eq!(deposit, 10 + metadata.encoded_size() as u128);
+		assert_eq!(para::Balances::reserved_balance(&registrar), deposit);
+		assert_eq!(AssetRegistry::deposits(1), Some((registrar.clone(), deposit)));
+
+		// a longer name needs a larger deposit
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			None,
+			Some(vec![b'a'; 50]),
+			None,
+			None,
+			None,
+			None
+		));
+		let deposit = AssetRegistry::deposit_for(&AssetRegistry::metadata(1).unwrap());
+		assert_eq!(para::Balances::reserved_balance(&registrar), deposit);
+		assert_eq!(AssetRegistry::deposits(1), Some((registrar.clone(), deposit)));
+
+		// no deposit is taken for registrations by root
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+		assert_eq!(AssetRegistry::deposits(2), None);
+
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::DepositReleased {
+			asset_id: 1,
+			depositor: registrar.clone(),
+			amount: deposit,
+		}));
+		assert_eq!(para::Balances::reserved_balance(&registrar), 0);
+		assert_eq!(AssetRegistry::deposits(1), None);
+
+		// the deposit must be affordable
+		para::Balances::make_free_balance_be(&registrar, 1);
+		assert_noop!(
+			AssetRegistry::register_asset(Origin::signed(registrar), dummy_metadata(), None),
+			pallet_balances::Error::<para::Runtime>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn test_permissionless_registration() {
+	use frame_support::traits::Currency;
+	use sp_runtime::traits::BadOrigin;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let alice = ALICE;
+		para::Balances::make_free_balance_be(&alice, 1_000);
+
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::signed(alice.clone()),
+			dummy_metadata(),
+			None
+		));
+		assert_eq!(
+			AssetRegistry::deposits(1).map(|(depositor, _)| depositor),
+			Some(alice.clone())
+		);
+
+		// only the authority can change the asset afterwards
+		assert_noop!(
+			AssetRegistry::update_asset(Origin::signed(alice.clone()), 1, Some(18), None, None, None, None, None),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::deregister_asset(Origin::signed(alice), 1, false),
+			BadOrigin
+		);
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::signed(para::Registrar::get()),
+			1,
+			Some(18),
+			None,
+			None,
+			None,
+			None,
+			None
+		));
+	});
+}
+
+#[test]
+fn test_resolve_unknown_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let sibling_asset = |para_id| MultiLocation::new(1, X2(Parachain(para_id), GeneralKey(b"TKN".to_vec())));
+
+		// the default is not to resolve anything
+		assert_eq!(AssetRegistry::fetch_metadata_by_location(&sibling_asset(2)), None);
+
+		para::ResolveUnknownLocations::set(true);
+		let metadata = AssetRegistry::fetch_metadata_by_location(&sibling_asset(2)).unwrap();
+		assert_eq!(metadata.symbol.to_vec(), b"TKN".to_vec());
+		assert_eq!(metadata.location, Some(sibling_asset(2).into()));
+		// but nothing is registered
+		assert_eq!(AssetRegistry::location_to_asset_id(sibling_asset(2)), None);
+		assert_eq!(AssetRegistry::metadata(1002), None);
+
+		// registering them is up to `register_unknown_location`, if enabled
+		assert_noop!(
+			AssetRegistry::register_unknown_location(&sibling_asset(2)),
+			Error::<para::Runtime>::AssetNotFound
+		);
+		para::AutoRegisterUnknownLocations::set(true);
+		assert_eq!(
+			AssetRegistry::fetch_metadata_by_location(&sibling_asset(2)),
+			Some(metadata.clone())
+		);
+		assert_eq!(AssetRegistry::location_to_asset_id(sibling_asset(2)), None);
+		assert_eq!(AssetRegistry::register_unknown_location(&sibling_asset(2)), Ok(1002));
+		assert_eq!(AssetRegistry::location_to_asset_id(sibling_asset(2)), Some(1002));
+		assert_eq!(AssetRegistry::metadata(1002), Some(metadata));
+		assert_eq!(AssetRegistry::last_asset_id(), 1002);
+		// registered locations resolve to their asset
+		assert_eq!(AssetRegistry::register_unknown_location(&sibling_asset(2)), Ok(1002));
+
+		// assets that can't be registered fail without a trace
+		assert_ok!(AssetRegistry::do_register_asset_without_asset_processor(
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			1003
+		));
+		assert_noop!(
+			AssetRegistry::register_unknown_location(&sibling_asset(3)),
+			Error::<para::Runtime>::ConflictingAssetId
+		);
+		// as do locations nothing is resolved for
+		assert_noop!(
+			AssetRegistry::register_unknown_location(&MultiLocation::parent()),
+			Error::<para::Runtime>::AssetNotFound
+		);
+
+		para::ResolveUnknownLocations::set(false);
+		para::AutoRegisterUnknownLocations::set(false);
+	});
+}
+
+#[test]
+fn test_asset_field_updated_events() {
+	use codec::Encode;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let field_updates = || {
+			para::System::events()
+				.into_iter()
+				.filter(|record| {
+					matches!(
+						record.event,
+						para::Event::AssetRegistry(crate::Event::AssetFieldUpdated { .. })
+					)
+				})
+				.count()
+		};
+
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		// disabled by default
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			Some(18),
+			None,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(field_updates(), 0);
+
+		para::EmitAssetFieldUpdates::set(true);
+		// the name is unchanged
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			Some(6),
+			Some(metadata.name.to_vec()),
+			None,
+			Some(100),
+			None,
+			None
+		));
+		assert_eq!(field_updates(), 2);
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::AssetFieldUpdated {
+			asset_id: 1,
+			field: AssetField::Decimals,
+			old: 18u32.encode(),
+			new: 6u32.encode(),
+		}));
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::AssetFieldUpdated {
+			asset_id: 1,
+			field: AssetField::ExistentialDeposit,
+			old: 0u128.encode(),
+			new: 100u128.encode(),
+		}));
+
+		para::EmitAssetFieldUpdates::set(false);
+	});
+}
+
+#[test]
+fn test_asset_updated_event() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		para::EmitPreviousMetadata::set(true);
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			Some(18),
+			None,
+			None,
+			None,
+			None,
+			None
+		));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::AssetUpdated {
+			asset_id: 1,
+			old: metadata.clone(),
+			new: AssetMetadata {
+				decimals: 18,
+				..metadata
+			},
+		}));
+
+		para::EmitPreviousMetadata::set(false);
+	});
+}
+
+#[test]
+fn test_native_relay_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_eq!(AssetRegistry::native_relay_asset_id(), None);
+		assert_noop!(
+			AssetRegistry::set_native_relay_asset(Origin::root(), 1),
+			Error::<para::Runtime>::AssetNotFound
+		);
+
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: Some(MultiLocation::parent().into()),
+				..dummy_metadata()
+			},
+			None
+		));
+		assert_ok!(AssetRegistry::set_native_relay_asset(Origin::root(), 1));
+		assert_eq!(AssetRegistry::native_relay_asset_id(), Some(1));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::SetNativeRelayAsset {
+			asset_id: 1,
+		}));
+
+		// deregistering another asset keeps it
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 2, false));
+		assert_eq!(AssetRegistry::native_relay_asset_id(), Some(1));
+
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+		assert_eq!(AssetRegistry::native_relay_asset_id(), None);
+	});
+}
+
+#[test]
+fn test_merge_additional() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		let update_fee = |fee_per_second| {
+			assert_ok!(AssetRegistry::update_asset(
+				Origin::root(),
+				1,
+				None,
+				None,
+				None,
+				None,
+				None,
+				Some(CustomMetadata { fee_per_second })
+			));
+			AssetRegistry::metadata(1).unwrap().additional.fee_per_second
+		};
+
+		para::MergeCustomMetadata::set(true);
+		assert_eq!(update_fee(0), metadata.additional.fee_per_second);
+		assert_eq!(update_fee(5), 5);
+
+		// replaced as a whole without merging
+		para::MergeCustomMetadata::set(false);
+		assert_eq!(update_fee(0), 0);
+	});
+}
+
+#[test]
+fn test_do_set_metadata() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata.clone(), None));
+
+		let old_location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		let new_location = MultiLocation::new(1, X2(Parachain(2), GeneralKey(vec![1])));
+		let new_metadata = AssetMetadata {
+			decimals: 18,
+			name: b"renamed".to_vec().try_into().unwrap(),
+			location: Some(new_location.clone().into()),
+			additional: CustomMetadata { fee_per_second: 0 },
+			..metadata
+		};
+
+		// replaced without merging
+		para::MergeCustomMetadata::set(true);
+		assert_ok!(AssetRegistry::do_set_metadata(1, new_metadata.clone()));
+		para::MergeCustomMetadata::set(false);
+
+		assert_eq!(AssetRegistry::metadata(1), Some(new_metadata.clone()));
+		assert_eq!(AssetRegistry::location_to_asset_id(old_location), None);
+		assert_eq!(AssetRegistry::location_to_asset_id(new_location), Some(1));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::UpdatedAsset {
+			asset_id: 1,
+			metadata: new_metadata.clone(),
+		}));
+
+		assert_noop!(
+			AssetRegistry::do_set_metadata(2, new_metadata),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn test_convert_amount() {
+	use sp_runtime::ArithmeticError;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::MaxDecimals::set(u32::MAX);
+
+		let register = |decimals| {
+			assert_ok!(AssetRegistry::register_asset(
+				Origin::root(),
+				AssetMetadata {
+					decimals,
+					location: None,
+					..dummy_metadata()
+				},
+				None
+			));
+		};
+		register(12);
+		register(18);
+		register(60);
+
+		assert_eq!(AssetRegistry::convert_amount(&1, &1, 1_234), Ok(1_234));
+		assert_eq!(AssetRegistry::convert_amount(&1, &2, 1_234), Ok(1_234_000_000));
+		assert_eq!(AssetRegistry::convert_amount(&2, &1, 1_234_567_890), Ok(1_234));
+		assert_eq!(AssetRegistry::convert_amount(&2, &1, 999_999), Ok(0));
+
+		assert_eq!(AssetRegistry::convert_amount(&1, &3, 0), Ok(0));
+		assert_eq!(
+			AssetRegistry::convert_amount(&1, &3, 1),
+			Err(ArithmeticError::Overflow.into())
+		);
+		assert_eq!(AssetRegistry::convert_amount(&3, &1, u128::MAX), Ok(0));
+
+		assert_noop!(
+			AssetRegistry::convert_amount(&1, &4, 1),
+			Error::<para::Runtime>::AssetNotFound
+		);
+
+		assert_eq!(AssetRegistry::rescale_amount(&1, 1_234, 15), Ok(1_234_000));
+		assert_eq!(AssetRegistry::rescale_amount(&2, 1_234_567, 12), Ok(1));
+		assert_eq!(
+			AssetRegistry::rescale_amount(&1, u128::MAX, 13),
+			Err(ArithmeticError::Overflow.into())
+		);
+		assert_noop!(
+			AssetRegistry::rescale_amount(&4, 1, 12),
+			Error::<para::Runtime>::AssetNotFound
+		);
+
+		para::MaxDecimals::set(38);
+	});
+}
+
+#[test]
+fn test_max_decimals() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_noop!(
+			AssetRegistry::register_asset(
+				Origin::root(),
+				AssetMetadata {
+					decimals: 39,
+					..dummy_metadata()
+				},
+				None
+			),
+			Error::<para::Runtime>::InvalidDecimals
+		);
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				decimals: 38,
+				..dummy_metadata()
+			},
+			None
+		));
+
+		assert_noop!(
+			AssetRegistry::update_asset(Origin::root(), 1, Some(39), None, None, None, None, None),
+			Error::<para::Runtime>::InvalidDecimals
+		);
+		assert_noop!(
+			AssetRegistry::update_asset(Origin::root(), 1, Some(u32::MAX), None, None, None, None, None),
+			Error::<para::Runtime>::InvalidDecimals
+		);
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			Some(0),
+			None,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			Some(38),
+			None,
+			None,
+			None,
+			None,
+			None
+		));
+
+		// existing assets can still be updated after lowering the maximum
+		para::MaxDecimals::set(18);
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			None,
+			Some(b"renamed".to_vec()),
+			None,
+			None,
+			None,
+			None
+		));
+		assert_noop!(
+			AssetRegistry::update_asset(Origin::root(), 1, Some(19), None, None, None, None, None),
+			Error::<para::Runtime>::InvalidDecimals
+		);
+
+		para::MaxDecimals::set(38);
+	});
+}
+
+#[test]
+fn test_zero_existential_deposit() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::AllowZeroExistentialDeposit::set(false);
+
+		assert_noop!(
+			AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None),
+			Error::<para::Runtime>::ZeroExistentialDeposit
+		);
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				existential_deposit: 1,
+				..dummy_metadata()
+			},
+			None
+		));
+		assert_noop!(
+			AssetRegistry::update_asset(Origin::root(), 1, None, None, None, Some(0), None, None),
+			Error::<para::Runtime>::ZeroExistentialDeposit
+		);
+
+		para::AllowZeroExistentialDeposit::set(true);
+	});
+}
+
+#[test]
+fn test_canonicalize_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let sibling_view = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![0])));
+		let local_view = MultiLocation::new(0, X1(GeneralKey(vec![0])));
+
+		assert_eq!(AssetRegistry::canonicalize_location(sibling_view.clone()), sibling_view);
+
+		para::CanonicalizeSelfLocation::set(true);
+		assert_eq!(AssetRegistry::canonicalize_location(sibling_view), local_view);
+		assert_eq!(AssetRegistry::canonicalize_location(local_view.clone()), local_view);
+		para::CanonicalizeSelfLocation::set(false);
+	});
+}
+
+#[test]
+fn test_local_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let here: VersionedMultiLocation = MultiLocation::here().into();
+
+		assert_noop!(
+			AssetRegistry::register_asset(
+				Origin::root(),
+				AssetMetadata {
+					location: Some(here.clone()),
+					..dummy_metadata()
+				},
+				None
+			),
+			Error::<para::Runtime>::LocalLocationNotAllowed
+		);
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		assert_noop!(
+			AssetRegistry::add_location(Origin::root(), 1, Box::new(here.clone())),
+			Error::<para::Runtime>::LocalLocationNotAllowed
+		);
+
+		para::AllowLocalLocation::set(true);
+
+		assert_ok!(AssetRegistry::add_location(Origin::root(), 1, Box::new(here)));
+		assert_eq!(AssetRegistry::location_to_asset_id(MultiLocation::here()), Some(1));
+
+		para::AllowLocalLocation::set(false);
+	});
+}
+
+#[test]
+fn test_unconvertible_location_is_bad_version() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		// a parent junction after an interior one has no v1 representation
+		let location = VersionedMultiLocation::V0(xcm::v0::MultiLocation::X2(
+			xcm::v0::Junction::Parachain(1),
+			xcm::v0::Junction::Parent,
+		));
+
+		assert_noop!(
+			AssetRegistry::register_asset(
+				Origin::root(),
+				AssetMetadata {
+					location: Some(location.clone()),
+					..dummy_metadata()
+				},
+				None
+			),
+			Error::<para::Runtime>::BadVersion
+		);
+
+		// deregistering such an asset fails without touching storage, even
+		// outside of a transactional extrinsic
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+		Metadata::<para::Runtime>::mutate(1, |metadata| metadata.as_mut().unwrap().location = Some(location));
+		assert_noop!(
+			AssetRegistry::do_deregister_asset(1, false),
+			Error::<para::Runtime>::BadVersion
+		);
+	});
+}
+
+#[test]
+fn test_with_metadata() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+
+		assert_eq!(
+			AssetRegistry::with_metadata(&1, |metadata| metadata.additional.fee_per_second),
+			Some(dummy_metadata().additional.fee_per_second)
+		);
+		assert_eq!(
+			AssetRegistry::with_metadata(&2, |metadata| metadata.additional.fee_per_second),
+			None
+		);
+	});
+}
+
+#[test]
+fn test_register_asset_with_deposit() {
+	use frame_support::traits::{Currency, ReservableCurrency};
+	use sp_runtime::traits::BadOrigin;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::Balances::make_free_balance_be(&ALICE, 1_000);
+
+		assert_noop!(
+			AssetRegistry::register_asset_with_deposit(Origin::root(), dummy_metadata()),
+			BadOrigin
+		);
+		assert_ok!(AssetRegistry::register_asset_with_deposit(
+			Origin::signed(ALICE),
+			dummy_metadata()
+		));
+		let deposit = AssetRegistry::deposit_for(&dummy_metadata());
+		assert_eq!(AssetRegistry::metadata(1), Some(dummy_metadata()));
+		assert_eq!(AssetRegistry::deposits(1), Some((ALICE, deposit)));
+		assert_eq!(para::Balances::reserved_balance(&ALICE), deposit);
+
+		// governance can still deregister it, returning what is left of the
+		// deposit
+		para::Balances::slash_reserved(&ALICE, 5);
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::DepositReleased {
+			asset_id: 1,
+			depositor: ALICE,
+			amount: deposit - 5,
+		}));
+		assert_eq!(para::Balances::reserved_balance(&ALICE), 0);
+
+		para::AllowRegistrationWithDeposit::set(false);
+		assert_noop!(
+			AssetRegistry::register_asset_with_deposit(Origin::signed(ALICE), dummy_metadata()),
+			Error::<para::Runtime>::RegistrationWithDepositDisabled
+		);
+		para::AllowRegistrationWithDeposit::set(true);
+	});
+}
+
+#[test]
+fn test_update_origin() {
+	use sp_runtime::traits::BadOrigin;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let updater = para::Updater::get();
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::signed(updater.clone()),
+			1,
+			None,
+			Some(b"renamed".to_vec()),
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(
+			AssetRegistry::metadata(1).unwrap().name.into_inner(),
+			b"renamed".to_vec()
+		);
+		assert_ok!(AssetRegistry::update_assets(Origin::signed(updater.clone()), vec![]));
+		assert_ok!(AssetRegistry::freeze_asset(Origin::signed(updater), 1));
+
+		// the deprecated `AuthorityOrigin` satisfies `UpdateOrigin` as well
+		assert_ok!(AssetRegistry::thaw_asset(Origin::signed(para::Registrar::get()), 1));
+
+		assert_noop!(AssetRegistry::freeze_asset(Origin::signed(ALICE), 1), BadOrigin);
+	});
+}
+
+#[test]
+fn test_set_sufficient() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		assert!(!AssetRegistry::is_sufficient(&1));
+
+		assert_ok!(AssetRegistry::set_sufficient(Origin::root(), 1, true));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::SetSufficient {
+			asset_id: 1,
+			sufficient: true,
+		}));
+		assert!(AssetRegistry::is_sufficient(&1));
+
+		assert_ok!(AssetRegistry::set_sufficient(Origin::root(), 1, false));
+		assert!(!AssetRegistry::is_sufficient(&1));
+
+		assert_ok!(AssetRegistry::set_sufficient(Origin::root(), 1, true));
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, true));
+		assert!(!AssetRegistry::is_sufficient(&1));
+
+		assert_noop!(
+			AssetRegistry::set_sufficient(Origin::root(), 1, true),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn test_asset_and_location_exist() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		let additional = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1])));
+		assert!(!AssetRegistry::asset_exists(&1));
+		assert!(!AssetRegistry::location_exists(&location));
+
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata, None));
+		assert_ok!(AssetRegistry::add_location(
+			Origin::root(),
+			1,
+			Box::new(additional.clone().into())
+		));
+
+		assert!(AssetRegistry::asset_exists(&1));
+		assert!(!AssetRegistry::asset_exists(&2));
+		assert!(AssetRegistry::location_exists(&location));
+		assert!(AssetRegistry::location_exists(&additional));
+		assert!(!AssetRegistry::location_exists(&MultiLocation::parent()));
+	});
+}
+
+#[test]
+fn test_swap_asset_ids() {
+	TestNet::reset();
+	para::EnforceUniqueSymbol::set(true);
+
+	ParaA::execute_with(|| {
+		let metadata_a = dummy_metadata();
+		let metadata_b = AssetMetadata {
+			symbol: b"paraB".to_vec().try_into().unwrap(),
+			location: Some(MultiLocation::new(1, X2(Parachain(2), GeneralKey(vec![0]))).into()),
+			..dummy_metadata()
+		};
+		let location_a: MultiLocation = metadata_a.location.clone().unwrap().try_into().unwrap();
+		let location_b: MultiLocation = metadata_b.location.clone().unwrap().try_into().unwrap();
+		let additional_a = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1])));
+
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata_a.clone(), None));
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata_b.clone(), None));
+		assert_ok!(AssetRegistry::add_location(
+			Origin::root(),
+			1,
+			Box::new(additional_a.clone().into())
+		));
+		assert_ok!(AssetRegistry::freeze_asset(Origin::root(), 1));
+		assert_ok!(AssetRegistry::set_native_relay_asset(Origin::root(), 2));
+
+		assert_ok!(AssetRegistry::swap_asset_ids(Origin::root(), 1, 2));
+		para::System::assert_has_event(para::Event::AssetRegistry(crate::Event::UpdatedAsset {
+			asset_id: 2,
+			metadata: metadata_a.clone(),
+		}));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::UpdatedAsset {
+			asset_id: 1,
+			metadata: metadata_b.clone(),
+		}));
+
+		assert_eq!(AssetRegistry::metadata(1), Some(metadata_b));
+		assert_eq!(AssetRegistry::metadata(2), Some(metadata_a.clone()));
+		assert_eq!(AssetRegistry::location_to_asset_id(location_a), Some(2));
+		assert_eq!(AssetRegistry::location_to_asset_id(location_b), Some(1));
+		assert_eq!(AssetRegistry::location_to_asset_id(additional_a.clone()), Some(2));
+		assert_eq!(AssetRegistry::additional_locations(2, additional_a.clone()), Some(()));
+		assert_eq!(AssetRegistry::additional_locations(1, additional_a), None);
+		assert_eq!(AssetRegistry::symbol_to_asset_id(metadata_a.symbol), Some(2));
+		assert_eq!(
+			AssetRegistry::symbol_to_asset_id(b"paraB".to_vec().try_into().unwrap()),
+			Some(1)
+		);
+		assert!(AssetRegistry::is_frozen(&2));
+		assert!(!AssetRegistry::is_frozen(&1));
+		assert_eq!(AssetRegistry::native_relay_asset_id(), Some(1));
+
+		assert_noop!(
+			AssetRegistry::swap_asset_ids(Origin::root(), 1, 3),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn test_allow_registered_assets_from() {
+	use frame_support::{parameter_types, traits::Contains};
+
+	parameter_types! {
+		pub AllowedPrefixes: Vec<MultiLocation> = vec![MultiLocation::new(1, X1(Parachain(1)))];
+	}
+	type Allowed = AllowRegisteredAssetsFrom<para::Runtime, AllowedPrefixes>;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata_a = dummy_metadata();
+		let metadata_b = AssetMetadata {
+			location: Some(MultiLocation::new(1, X2(Parachain(2), GeneralKey(vec![0]))).into()),
+			..dummy_metadata()
+		};
+		let location_a: MultiLocation = metadata_a.location.clone().unwrap().try_into().unwrap();
+		let location_b: MultiLocation = metadata_b.location.clone().unwrap().try_into().unwrap();
+		let unregistered = MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1])));
+
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata_a, None));
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), metadata_b, None));
+
+		assert!(<Allowed as Contains<MultiLocation>>::contains(&location_a));
+		assert!(!<Allowed as Contains<MultiLocation>>::contains(&location_b));
+		assert!(!<Allowed as Contains<MultiLocation>>::contains(&unregistered));
+
+		assert!(<Allowed as Contains<MultiAsset>>::contains(&(location_a, 1).into()));
+		assert!(!<Allowed as Contains<MultiAsset>>::contains(&(location_b, 1).into()));
+		assert!(!<Allowed as Contains<MultiAsset>>::contains(
+			&(AssetId::Abstract(vec![0]), 1).into()
+		));
+	});
+}
+
+#[test]
+fn test_registered_assets() {
+	use frame_support::traits::Contains;
+
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert!(!RegisteredAssets::<para::Runtime>::contains(&1));
+
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		assert!(RegisteredAssets::<para::Runtime>::contains(&1));
+		assert!(!RegisteredAssets::<para::Runtime>::contains(&2));
+
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+		assert!(!RegisteredAssets::<para::Runtime>::contains(&1));
+	});
+}
+
+#[test]
+fn test_asset_timestamps() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::System::set_block_number(5);
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		assert_eq!(AssetRegistry::registered_at(&1), Some(5));
+		assert_eq!(AssetRegistry::updated_at(&1), Some(5));
+
+		para::System::set_block_number(7);
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			Some(18),
+			None,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(AssetRegistry::registered_at(&1), Some(5));
+		assert_eq!(AssetRegistry::updated_at(&1), Some(7));
+
+		para::System::set_block_number(9);
+		assert_ok!(AssetRegistry::set_location(
+			Origin::root(),
+			1,
+			Box::new(MultiLocation::new(1, X2(Parachain(1), GeneralKey(vec![1]))).into())
+		));
+		assert_eq!(AssetRegistry::updated_at(&1), Some(9));
+
+		// assets registered before timestamps were tracked
+		AssetTimestamps::<para::Runtime>::remove(1);
+		assert_eq!(AssetRegistry::registered_at(&1), None);
+		para::System::set_block_number(11);
+		assert_ok!(AssetRegistry::update_asset(
+			Origin::root(),
+			1,
+			Some(12),
+			None,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(AssetRegistry::registered_at(&1), Some(11));
+		assert_eq!(AssetRegistry::updated_at(&1), Some(11));
+
+		assert_ok!(AssetRegistry::deregister_asset(Origin::root(), 1, false));
+		assert_eq!(AssetRegistry::registered_at(&1), None);
+	});
+}
+
+#[test]
+fn test_get_or_register() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_eq!(AssetRegistry::get_or_register(dummy_metadata(), None), Ok(1));
+		para::System::assert_last_event(para::Event::AssetRegistry(crate::Event::RegisteredAsset {
+			asset_id: 1,
+			metadata: dummy_metadata(),
+		}));
+		let events = para::System::events().len();
+
+		// matching location
+		assert_eq!(AssetRegistry::get_or_register(dummy_metadata(), None), Ok(1));
+		// matching explicit id
+		assert_eq!(
+			AssetRegistry::get_or_register(
+				AssetMetadata {
+					location: None,
+					..dummy_metadata()
+				},
+				Some(1)
+			),
+			Ok(1)
+		);
+		assert_eq!(para::System::events().len(), events);
+
+		let metadata = AssetMetadata {
+			location: Some(MultiLocation::new(1, X2(Parachain(2), GeneralKey(vec![0]))).into()),
+			..dummy_metadata()
+		};
+		assert_eq!(AssetRegistry::get_or_register(metadata.clone(), None), Ok(2));
+		assert_eq!(AssetRegistry::metadata(2), Some(metadata));
+	});
+}
+
+#[test]
+fn test_reserve_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let sibling = MultiLocation::new(1, X1(Parachain(1)));
+		assert_ok!(AssetRegistry::register_asset(Origin::root(), dummy_metadata(), None));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: Some(MultiLocation::parent().into()),
+				..dummy_metadata()
+			},
+			None
+		));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: Some(MultiLocation::new(0, X1(GeneralKey(vec![1]))).into()),
+				..dummy_metadata()
+			},
+			None
+		));
+		assert_ok!(AssetRegistry::register_asset(
+			Origin::root(),
+			AssetMetadata {
+				location: None,
+				..dummy_metadata()
+			},
+			None
+		));
+
+		assert_eq!(AssetRegistry::reserve_location(&1), Some(sibling.clone()));
+		assert_eq!(AssetRegistry::reserve_location(&2), Some(MultiLocation::parent()));
+		assert_eq!(AssetRegistry::reserve_location(&3), Some(MultiLocation::here()));
+		assert_eq!(AssetRegistry::reserve_location(&4), None);
+		assert_eq!(AssetRegistry::reserve_location(&5), None);
+
+		assert!(AssetRegistry::is_reserve_asset(&1, &sibling));
+		assert!(!AssetRegistry::is_reserve_asset(&1, &MultiLocation::parent()));
+		assert!(AssetRegistry::is_reserve_asset(&2, &MultiLocation::parent()));
+		assert!(!AssetRegistry::is_reserve_asset(&4, &sibling));
+	});
+}
@@ -1,3 +1,10 @@
+//! Placeholder weights for orml_asset_registry
+//!
+//! THESE WEIGHTS ARE NOT BENCHMARK OUTPUT. They are hand-written counts of
+//! the storage reads and writes of each extrinsic, without any execution
+//! time. Runtimes should generate their own `WeightInfo` from the benchmarks
+//! in `benchmarking.rs` with the weight template.
+
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -11,19 +18,123 @@ use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
 	fn register_asset() -> Weight;
+	fn register_asset_without_location() -> Weight;
+	fn register_asset_batch(n: u32, ) -> Weight;
 	fn update_asset() -> Weight;
+	fn update_asset_decimals() -> Weight;
+	fn update_asset_name() -> Weight;
+	fn update_asset_symbol() -> Weight;
+	fn update_asset_existential_deposit() -> Weight;
+	fn update_asset_location() -> Weight;
+	fn update_asset_additional() -> Weight;
 	fn set_asset_location() -> Weight;
+	fn deregister_asset(n: u32, ) -> Weight;
+	fn freeze_asset() -> Weight;
+	fn thaw_asset() -> Weight;
+	fn set_last_asset_id() -> Weight;
+	fn set_xcm_enabled() -> Weight;
+	fn force_set_location() -> Weight;
+	fn add_location(n: u32, ) -> Weight;
+	fn remove_location() -> Weight;
+	fn set_native_relay_asset() -> Weight;
+	fn set_sufficient() -> Weight;
+	fn swap_asset_ids(n: u32, ) -> Weight;
+	fn register_asset_with_deposit() -> Weight;
 }
 
 /// Default weights.
 impl WeightInfo for () {
 	fn register_asset() -> Weight {
-		0
+		(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn register_asset_without_location() -> Weight {
+		(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn register_asset_batch(n: u32, ) -> Weight {
+		(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((7 as Weight).saturating_mul(n as Weight)))
 	}
 	fn update_asset() -> Weight {
-		0
+		(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn update_asset_decimals() -> Weight {
+		(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn update_asset_name() -> Weight {
+		(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn update_asset_symbol() -> Weight {
+		(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn update_asset_existential_deposit() -> Weight {
+		(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn update_asset_location() -> Weight {
+		(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn update_asset_additional() -> Weight {
+		(RocksDbWeight::get().writes(2 as Weight))
 	}
 	fn set_asset_location() -> Weight {
-		0
+		(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn deregister_asset(n: u32, ) -> Weight {
+		(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn freeze_asset() -> Weight {
+		(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn thaw_asset() -> Weight {
+		(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_last_asset_id() -> Weight {
+		(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_xcm_enabled() -> Weight {
+		(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn force_set_location() -> Weight {
+		(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn add_location(n: u32, ) -> Weight {
+		(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_location() -> Weight {
+		(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_native_relay_asset() -> Weight {
+		(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_sufficient() -> Weight {
+		(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn swap_asset_ids(n: u32, ) -> Weight {
+		(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn register_asset_with_deposit() -> Weight {
+		(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
 	}
 }
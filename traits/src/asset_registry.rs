@@ -1,5 +1,6 @@
-use frame_support::pallet_prelude::*;
-use xcm::latest::prelude::*;
+use frame_support::{pallet_prelude::*, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
+use sp_std::vec::Vec;
+use xcm::{latest::prelude::*, VersionedMultiLocation};
 
 pub trait WeightToFeeConverter {
 	fn convert_weight_to_fee(location: &MultiLocation, weight: Weight) -> Option<u128>;
@@ -9,9 +10,143 @@ pub trait FixedConversionRateProvider {
 	fn get_fee_per_second(location: &MultiLocation) -> Option<u128>;
 }
 
+/// Maps locations to a canonical form before they are used as keys, so that
+/// different representations of the same location are treated as equal.
+pub trait LocationCanonicalizer {
+	fn canonicalize(location: MultiLocation) -> MultiLocation;
+}
+
+/// Keeps locations as they are.
+impl LocationCanonicalizer for () {
+	fn canonicalize(location: MultiLocation) -> MultiLocation {
+		location
+	}
+}
+
+/// Combines the custom metadata of an asset with the `additional` value of an
+/// update, so that structured metadata can be updated partially.
+pub trait MergeAdditional<CustomMetadata> {
+	fn merge_additional(old: &CustomMetadata, patch: CustomMetadata) -> CustomMetadata;
+}
+
+/// Replaces the custom metadata with the new value.
+impl<CustomMetadata> MergeAdditional<CustomMetadata> for () {
+	fn merge_additional(_old: &CustomMetadata, patch: CustomMetadata) -> CustomMetadata {
+		patch
+	}
+}
+
 pub trait AssetProcessor<AssetId, Metadata> {
 	fn pre_register(id: Option<AssetId>, asset_metadata: Metadata) -> Result<(AssetId, Metadata), DispatchError>;
 	fn post_register(_id: AssetId, _asset_metadata: Metadata) -> Result<(), DispatchError> {
 		Ok(())
 	}
+	/// Called before an asset is updated with its current metadata and the
+	/// metadata it would have after the update. Returns the metadata to
+	/// store, or an error to reject the update.
+	fn pre_update(_id: &AssetId, _old_metadata: &Metadata, new_metadata: Metadata) -> Result<Metadata, DispatchError> {
+		Ok(new_metadata)
+	}
+	/// Called after an asset has been updated with its new metadata.
+	fn post_update(_id: &AssetId, _asset_metadata: &Metadata) -> Result<(), DispatchError> {
+		Ok(())
+	}
+	/// Called when metadata is requested for a location that isn't
+	/// registered. May derive an id and metadata for it, e.g. for derivative
+	/// assets, which are registered on the fly if the registry is configured
+	/// to do so.
+	fn resolve_unknown_location(_location: &MultiLocation) -> Option<(AssetId, Metadata)> {
+		None
+	}
+	/// Called with the id of every registered asset, including those
+	/// registered without going through `pre_register`.
+	fn note_asset_id(_id: &AssetId) {}
+	/// Check the invariants of any state kept by the processor.
+	fn try_state() -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
+/// Data describing the asset properties.
+#[derive(TypeInfo, Encode, Decode, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+#[scale_info(skip_type_params(StringLimit))]
+pub struct AssetMetadata<Balance, CustomMetadata, StringLimit>
+where
+	Balance: Parameter,
+	CustomMetadata: Parameter + Member + TypeInfo,
+	StringLimit: Get<u32>,
+{
+	pub decimals: u32,
+	pub name: BoundedVec<u8, StringLimit>,
+	pub symbol: BoundedVec<u8, StringLimit>,
+	pub existential_deposit: Balance,
+	pub location: Option<VersionedMultiLocation>,
+	pub additional: CustomMetadata,
+}
+
+/// Read access to the asset registry.
+///
+/// Implemented by `orml_asset_registry::Pallet`. Pallets that only read the
+/// registry should depend on this trait rather than on the pallet, so that
+/// their tests can provide a simple implementation instead.
+pub trait Inspect {
+	/// AssetId type
+	type AssetId;
+	/// Balance type
+	type Balance: Parameter;
+	/// Custom metadata type
+	type CustomMetadata: Parameter + Member + TypeInfo;
+	/// Name and symbol string limit
+	type StringLimit: Get<u32>;
+
+	/// The asset id registered for the given location.
+	fn asset_id(location: &MultiLocation) -> Option<Self::AssetId>;
+	/// The metadata of the given asset.
+	fn metadata(
+		asset_id: &Self::AssetId,
+	) -> Option<AssetMetadata<Self::Balance, Self::CustomMetadata, Self::StringLimit>>;
+	/// The metadata of the asset registered for the given location.
+	fn metadata_by_location(
+		location: &MultiLocation,
+	) -> Option<AssetMetadata<Self::Balance, Self::CustomMetadata, Self::StringLimit>>;
+	/// The location of the given asset, if any.
+	fn location(asset_id: &Self::AssetId) -> Result<Option<MultiLocation>, DispatchError>;
+	/// The metadata of the given asset, or an error if it isn't registered.
+	///
+	/// Example:
+	/// ```ignore
+	/// let metadata = T::AssetRegistry::ensure_asset_exists(&asset_id)?;
+	/// ensure!(metadata.decimals <= 18, Error::<T>::UnsupportedDecimals);
+	/// ```
+	fn ensure_asset_exists(
+		asset_id: &Self::AssetId,
+	) -> Result<AssetMetadata<Self::Balance, Self::CustomMetadata, Self::StringLimit>, DispatchError> {
+		Self::metadata(asset_id).ok_or(DispatchError::Other("AssetNotFound"))
+	}
+}
+
+/// Write access to the asset registry, for pallets that register or update
+/// assets themselves. Unlike the extrinsics, these don't check any origin
+/// and take no deposit.
+pub trait Mutate: Inspect {
+	/// Register a new asset, with the given id or one assigned by the
+	/// registry, and return its id.
+	fn register_asset(
+		metadata: AssetMetadata<Self::Balance, Self::CustomMetadata, Self::StringLimit>,
+		asset_id: Option<Self::AssetId>,
+	) -> Result<Self::AssetId, DispatchError>;
+	/// Update the fields of an existing asset that are `Some`.
+	#[allow(clippy::too_many_arguments)]
+	fn update_asset(
+		asset_id: Self::AssetId,
+		decimals: Option<u32>,
+		name: Option<Vec<u8>>,
+		symbol: Option<Vec<u8>>,
+		existential_deposit: Option<Self::Balance>,
+		location: Option<Option<VersionedMultiLocation>>,
+		additional: Option<Self::CustomMetadata>,
+	) -> DispatchResult;
+	/// Update only the custom metadata of an existing asset by applying `f`
+	/// to it. The result replaces the stored value as a whole.
+	fn mutate_additional(asset_id: Self::AssetId, f: impl FnOnce(&mut Self::CustomMetadata)) -> DispatchResult;
 }
@@ -78,12 +78,65 @@ impl Reserve for RelativeReserveProvider {
 
 pub trait RelativeLocations {
 	fn sibling_parachain_general_key(para_id: u32, general_key: Vec<u8>) -> MultiLocation;
+	/// The location of an asset keyed by `GeneralIndex` on a sibling
+	/// parachain, e.g. a `pallet-assets` asset on Statemint.
+	fn sibling_parachain_general_index(para_id: u32, index: u128) -> MultiLocation;
+	/// The location of an asset behind a specific pallet instance on a sibling
+	/// parachain, e.g. `(1, X3(Parachain(1000), PalletInstance(50),
+	/// GeneralIndex(id)))` for Statemint assets.
+	fn sibling_parachain_pallet_instance(para_id: u32, pallet: u8, index: u128) -> MultiLocation;
+	/// The location of an asset keyed by `GeneralKey` on the relay chain.
+	fn parent_general_key(general_key: Vec<u8>) -> MultiLocation;
+	/// The location of an account on a sibling parachain using 20-byte
+	/// (e.g. Ethereum-style) account ids, on any network.
+	fn sibling_parachain_account_key20(para_id: u32, key: [u8; 20]) -> MultiLocation;
+	/// The location of an account on a sibling parachain using 32-byte
+	/// account ids, on any network.
+	fn sibling_parachain_account_id32(para_id: u32, id: [u8; 32]) -> MultiLocation;
 }
 
 impl RelativeLocations for MultiLocation {
 	fn sibling_parachain_general_key(para_id: u32, general_key: Vec<u8>) -> MultiLocation {
 		MultiLocation::new(1, X2(Parachain(para_id), GeneralKey(general_key)))
 	}
+
+	fn sibling_parachain_general_index(para_id: u32, index: u128) -> MultiLocation {
+		MultiLocation::new(1, X2(Parachain(para_id), GeneralIndex(index)))
+	}
+
+	fn sibling_parachain_pallet_instance(para_id: u32, pallet: u8, index: u128) -> MultiLocation {
+		MultiLocation::new(1, X3(Parachain(para_id), PalletInstance(pallet), GeneralIndex(index)))
+	}
+
+	fn parent_general_key(general_key: Vec<u8>) -> MultiLocation {
+		MultiLocation::new(1, X1(GeneralKey(general_key)))
+	}
+
+	fn sibling_parachain_account_key20(para_id: u32, key: [u8; 20]) -> MultiLocation {
+		MultiLocation::new(
+			1,
+			X2(
+				Parachain(para_id),
+				AccountKey20 {
+					network: NetworkId::Any,
+					key,
+				},
+			),
+		)
+	}
+
+	fn sibling_parachain_account_id32(para_id: u32, id: [u8; 32]) -> MultiLocation {
+		MultiLocation::new(
+			1,
+			X2(
+				Parachain(para_id),
+				AccountId32 {
+					network: NetworkId::Any,
+					id,
+				},
+			),
+		)
+	}
 }
 
 #[cfg(test)]
@@ -168,4 +221,63 @@ mod tests {
 			Some(GENERAL_INDEX.into())
 		);
 	}
+
+	#[test]
+	fn sibling_parachain_general_index_works() {
+		assert_eq!(
+			MultiLocation::sibling_parachain_general_index(1, 1),
+			MultiLocation::new(1, X2(PARACHAIN, GENERAL_INDEX))
+		);
+	}
+
+	#[test]
+	fn sibling_parachain_pallet_instance_works() {
+		let location = MultiLocation::sibling_parachain_pallet_instance(1000, 50, 1984);
+		assert_eq!(
+			location,
+			MultiLocation::new(1, X3(Parachain(1000), PalletInstance(50), GeneralIndex(1984)))
+		);
+		assert_eq!(location.chain_part(), Some(MultiLocation::new(1, X1(Parachain(1000)))));
+		assert_eq!(
+			location.non_chain_part(),
+			Some((PalletInstance(50), GeneralIndex(1984)).into())
+		);
+	}
+
+	#[test]
+	fn parent_general_key_works() {
+		let location = MultiLocation::parent_general_key("DOT".into());
+		assert_eq!(location, MultiLocation::new(1, X1(GeneralKey("DOT".into()))));
+		assert_eq!(location.chain_part(), Some(MultiLocation::parent()));
+	}
+
+	#[test]
+	fn sibling_parachain_accounts_work() {
+		assert_eq!(
+			MultiLocation::sibling_parachain_account_key20(1, [1; 20]),
+			MultiLocation::new(
+				1,
+				X2(
+					PARACHAIN,
+					AccountKey20 {
+						network: NetworkId::Any,
+						key: [1; 20]
+					}
+				)
+			)
+		);
+		assert_eq!(
+			MultiLocation::sibling_parachain_account_id32(1, [1; 32]),
+			MultiLocation::new(
+				1,
+				X2(
+					PARACHAIN,
+					AccountId32 {
+						network: NetworkId::Any,
+						id: [1; 32]
+					}
+				)
+			)
+		);
+	}
 }
@@ -3,9 +3,28 @@ use xcm::latest::prelude::*;
 
 use crate::location::RelativeLocations;
 
+/// Constructors and accessors for concrete fungible assets.
+///
+/// Like the rest of ORML, this works with the `xcm::latest` types, which are
+/// the same as those of the asset registry.
 pub trait ConcreteFungibleAsset {
 	fn sibling_parachain_asset(para_id: u32, general_key: Vec<u8>, amount: u128) -> MultiAsset;
+	/// Like `sibling_parachain_asset`, but fails for empty keys and keys that
+	/// consist only of zero bytes, which some chains reject.
+	#[allow(clippy::result_unit_err)]
+	fn try_sibling_parachain_asset(para_id: u32, general_key: Vec<u8>, amount: u128) -> Result<MultiAsset, ()>;
+	fn sibling_parachain_general_index_asset(para_id: u32, index: u128, amount: u128) -> MultiAsset;
+	fn sibling_parachain_pallet_instance_asset(para_id: u32, pallet: u8, index: u128, amount: u128) -> MultiAsset;
 	fn parent_asset(amount: u128) -> MultiAsset;
+	fn parent_general_key_asset(general_key: Vec<u8>, amount: u128) -> MultiAsset;
+	fn local_asset(general_index: u128, amount: u128) -> MultiAsset;
+	fn here_asset(amount: u128) -> MultiAsset;
+	/// Returns true if the asset is identified by the concrete `location`,
+	/// regardless of its fungibility.
+	fn matches_location(&self, location: &MultiLocation) -> bool;
+	/// Returns the location and amount of a concrete fungible asset, or `None`
+	/// for abstract or non-fungible assets.
+	fn concrete_fungible(&self) -> Option<(MultiLocation, u128)>;
 }
 
 impl ConcreteFungibleAsset for MultiAsset {
@@ -17,7 +36,124 @@ impl ConcreteFungibleAsset for MultiAsset {
 			.into()
 	}
 
+	fn try_sibling_parachain_asset(para_id: u32, general_key: Vec<u8>, amount: u128) -> Result<MultiAsset, ()> {
+		if general_key.iter().all(|byte| *byte == 0) {
+			return Err(());
+		}
+		Ok(Self::sibling_parachain_asset(para_id, general_key, amount))
+	}
+
+	fn sibling_parachain_general_index_asset(para_id: u32, index: u128, amount: u128) -> MultiAsset {
+		(MultiLocation::sibling_parachain_general_index(para_id, index), amount).into()
+	}
+
+	fn sibling_parachain_pallet_instance_asset(para_id: u32, pallet: u8, index: u128, amount: u128) -> MultiAsset {
+		(
+			MultiLocation::sibling_parachain_pallet_instance(para_id, pallet, index),
+			amount,
+		)
+			.into()
+	}
+
 	fn parent_asset(amount: u128) -> MultiAsset {
 		(MultiLocation::parent(), amount).into()
 	}
+
+	fn parent_general_key_asset(general_key: Vec<u8>, amount: u128) -> MultiAsset {
+		(MultiLocation::parent_general_key(general_key), amount).into()
+	}
+
+	fn local_asset(general_index: u128, amount: u128) -> MultiAsset {
+		(MultiLocation::new(0, X1(GeneralIndex(general_index))), amount).into()
+	}
+
+	fn here_asset(amount: u128) -> MultiAsset {
+		(MultiLocation::here(), amount).into()
+	}
+
+	fn matches_location(&self, location: &MultiLocation) -> bool {
+		matches!(&self.id, Concrete(id) if id == location)
+	}
+
+	fn concrete_fungible(&self) -> Option<(MultiLocation, u128)> {
+		match (&self.id, &self.fun) {
+			(Concrete(location), Fungible(amount)) => Some((location.clone(), *amount)),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn concrete_fungible_works() {
+		let asset = MultiAsset::sibling_parachain_asset(1, vec![0], 100);
+		let location = MultiLocation::sibling_parachain_general_key(1, vec![0]);
+
+		assert!(asset.matches_location(&location));
+		assert!(!asset.matches_location(&MultiLocation::parent()));
+		assert_eq!(asset.concrete_fungible(), Some((location, 100)));
+		assert_eq!(
+			MultiAsset::here_asset(1).concrete_fungible(),
+			Some((MultiLocation::here(), 1))
+		);
+		assert_eq!(
+			MultiAsset::local_asset(7, 100).concrete_fungible(),
+			Some((MultiLocation::new(0, X1(GeneralIndex(7))), 100))
+		);
+		assert_eq!(
+			MultiAsset::sibling_parachain_pallet_instance_asset(1000, 50, 1984, 100).concrete_fungible(),
+			Some((
+				MultiLocation::new(1, X3(Parachain(1000), PalletInstance(50), GeneralIndex(1984))),
+				100
+			))
+		);
+		assert_eq!(
+			MultiAsset::parent_general_key_asset(vec![0], 100).concrete_fungible(),
+			Some((MultiLocation::new(1, X1(GeneralKey(vec![0]))), 100))
+		);
+		assert_eq!(
+			MultiAsset::sibling_parachain_general_index_asset(1000, 1984, 100).concrete_fungible(),
+			Some((MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(1984))), 100))
+		);
+	}
+
+	#[test]
+	fn try_sibling_parachain_asset_rejects_degenerate_keys() {
+		assert_eq!(MultiAsset::try_sibling_parachain_asset(1, vec![], 100), Err(()));
+		assert_eq!(MultiAsset::try_sibling_parachain_asset(1, vec![0; 32], 100), Err(()));
+		assert_eq!(
+			MultiAsset::try_sibling_parachain_asset(1, vec![0, 1], 100),
+			Ok(MultiAsset::sibling_parachain_asset(1, vec![0, 1], 100))
+		);
+	}
+
+	#[test]
+	fn uses_xcm_v2_types() {
+		// the asset registry and runtimes on this XCM release use v2
+		let asset: xcm::v2::MultiAsset = MultiAsset::parent_asset(1);
+		assert_eq!(asset.concrete_fungible(), Some((xcm::v2::MultiLocation::parent(), 1)));
+	}
+
+	#[test]
+	fn parent_general_key_asset_is_reserved_on_relay() {
+		use crate::location::{AbsoluteReserveProvider, RelativeReserveProvider, Reserve};
+
+		let asset = MultiAsset::parent_general_key_asset("USDT".into(), 100);
+		assert_eq!(AbsoluteReserveProvider::reserve(&asset), Some(MultiLocation::parent()));
+		assert_eq!(RelativeReserveProvider::reserve(&asset), Some(MultiLocation::parent()));
+	}
+
+	#[test]
+	fn concrete_fungible_rejects_non_fungible_and_abstract_assets() {
+		let non_fungible: MultiAsset = (MultiLocation::parent(), Index(1)).into();
+		assert!(non_fungible.matches_location(&MultiLocation::parent()));
+		assert_eq!(non_fungible.concrete_fungible(), None);
+
+		let abstract_asset: MultiAsset = (Abstract(vec![0]), 100).into();
+		assert!(!abstract_asset.matches_location(&MultiLocation::parent()));
+		assert_eq!(abstract_asset.concrete_fungible(), None);
+	}
 }